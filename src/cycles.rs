@@ -0,0 +1,204 @@
+/// Finding loops in sequences of states, and lining up events that repeat periodically.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The shape of a sequence that eventually repeats: `prefix` states lead into a loop of
+/// `period` states, so state `prefix + period` is the same as state `prefix`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The index of the first state that is the same as state `n`.
+    #[must_use]
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.period
+        }
+    }
+}
+
+/// Finds the cycle reached from `start` with Brent's algorithm. Only a couple of states are
+/// kept at a time, at the cost of running `next` more often than [`find_cycle`] does.
+pub fn brent<S: Clone + PartialEq>(start: S, mut next: impl FnMut(&S) -> S) -> Cycle {
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = next(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = next(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = next(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        prefix += 1;
+    }
+
+    Cycle { prefix, period }
+}
+
+/// Finds the cycle reached from `start` by remembering every state, stopping at the first
+/// repeat. Also returns the `prefix + period` distinct states, in order.
+pub fn find_cycle<S: Clone + Eq + Hash>(
+    start: S,
+    mut next: impl FnMut(&S) -> S,
+) -> (Cycle, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = start;
+    loop {
+        if let Some(&prefix) = seen.get(&state) {
+            let period = history.len() - prefix;
+            return (Cycle { prefix, period }, history);
+        }
+        seen.insert(state.clone(), history.len());
+        let following = next(&state);
+        history.push(state);
+        state = following;
+    }
+}
+
+/// The state after `n` steps from `start`, skipping over whole loops once one is found.
+pub fn nth_state<S: Clone + Eq + Hash>(start: S, mut next: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = start;
+    for step in 0..n {
+        if let Some(&prefix) = seen.get(&state) {
+            let cycle = Cycle {
+                prefix,
+                period: step - prefix,
+            };
+            return history.swap_remove(cycle.reduce(n));
+        }
+        seen.insert(state.clone(), step);
+        let following = next(&state);
+        history.push(state);
+        state = following;
+    }
+    state
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Something that first happens at `offset`, then again every `period` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Periodic {
+    pub offset: usize,
+    pub period: usize,
+}
+
+impl Periodic {
+    #[must_use]
+    pub fn new(offset: usize, period: usize) -> Self {
+        assert!(period > 0, "period should be positive");
+        Self { offset, period }
+    }
+
+    /// The times when both `self` and `other` happen, or `None` if they never line up.
+    /// This is the Chinese remainder theorem, without needing coprime periods; when both
+    /// offsets are zero it is the same as taking the lowest common multiple.
+    #[must_use]
+    pub fn combine(self, other: Self) -> Option<Self> {
+        let (a, m) = (self.offset as i128, self.period as i128);
+        let (b, n) = (other.offset as i128, other.period as i128);
+
+        let (gcd, inverse, _) = extended_gcd(m, n);
+        if (b - a) % gcd != 0 {
+            return None;
+        }
+        let lcm = m / gcd * n;
+
+        // a + m * k lines up with `other` for k = (b - a) / gcd * inverse, modulo n / gcd
+        let k = ((b - a) / gcd * inverse).rem_euclid(n / gcd);
+        let mut time = a + m * k;
+        if time < b {
+            time += (b - time + lcm - 1) / lcm * lcm;
+        }
+
+        Some(Self::new(
+            usize::try_from(time).ok()?,
+            usize::try_from(lcm).ok()?,
+        ))
+    }
+
+    /// The times when everything in `events` happens at once.
+    pub fn combine_all(events: impl IntoIterator<Item = Self>) -> Option<Self> {
+        let mut events = events.into_iter();
+        let first = events.next()?;
+        events.try_fold(first, Self::combine)
+    }
+}
+
+/// Returns `(gcd, x, y)` such that `a * x + b * y == gcd`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (gcd, x, y) = extended_gcd(b, a % b);
+        (gcd, y, x - a / b * y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(&x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn test_brent_matches_hashing() {
+        for start in 0..255 {
+            let (cycle, history) = find_cycle(start, next);
+            assert_eq!(brent(start, next), cycle);
+            assert_eq!(history.len(), cycle.prefix + cycle.period);
+        }
+        assert_eq!(
+            brent(3, next),
+            Cycle {
+                prefix: 2,
+                period: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_nth_state() {
+        let mut state = 3;
+        for n in 0..50 {
+            assert_eq!(nth_state(3, next, n), state, "step {n}");
+            state = next(&state);
+        }
+        assert_eq!(nth_state(0u8, |&x| x.wrapping_add(1), 1_000_003), 3 + 64);
+    }
+
+    #[test]
+    fn test_combine() {
+        let lcm = Periodic::combine_all([6, 4, 10].map(|p| Periodic::new(p, p)));
+        assert_eq!(lcm, Some(Periodic::new(60, 60)));
+
+        // 3 mod 4 and 5 mod 6, both first happening later than the smallest solution
+        let both = Periodic::new(7, 4).combine(Periodic::new(17, 6));
+        assert_eq!(both, Some(Periodic::new(23, 12)));
+
+        assert_eq!(Periodic::new(0, 4).combine(Periodic::new(1, 2)), None);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::random::Rng;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(1);
+
+pub fn extract_digits(line: &str) -> Vec<u8> {
+    line.as_bytes()
+        .iter()
+        .filter_map(|b| {
+            if b.is_ascii_digit() {
+                Some(b - b'0')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The lines of the calibration document.
+pub fn parse(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(
+        parse(input)
+            .into_iter()
+            .map(|line| {
+                let digits = extract_digits(line);
+                let first = digits.first().unwrap();
+                let last = digits.last().unwrap();
+                u32::from(first * 10 + last)
+            })
+            .sum(),
+    )
+}
+
+pub fn extract_word_digits(line: &str) -> Vec<u8> {
+    let mut digits: Vec<u8> = vec![];
+    let mut b = line.as_bytes();
+    while !b.is_empty() {
+        match b {
+            b if b.starts_with(b"one") => {
+                digits.push(1);
+            }
+            b if b.starts_with(b"two") => {
+                digits.push(2);
+            }
+            b if b.starts_with(b"three") => {
+                digits.push(3);
+            }
+            b if b.starts_with(b"four") => {
+                digits.push(4);
+            }
+            b if b.starts_with(b"five") => {
+                digits.push(5);
+            }
+            b if b.starts_with(b"six") => {
+                digits.push(6);
+            }
+            b if b.starts_with(b"seven") => {
+                digits.push(7);
+            }
+            b if b.starts_with(b"eight") => {
+                digits.push(8);
+            }
+            b if b.starts_with(b"nine") => {
+                digits.push(9);
+            }
+            b if b[0].is_ascii_digit() => digits.push(b[0] - b'0'),
+            _ => {}
+        }
+        b = &b[1..];
+    }
+
+    digits
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    Some(
+        parse(input)
+            .into_iter()
+            .map(|line| {
+                let digits = extract_word_digits(line);
+                let first = digits.first().unwrap();
+                let last = digits.last().unwrap();
+                u32::from(first * 10 + last)
+            })
+            .sum(),
+    )
+}
+
+/// A random calibration document of `size` lines, mixing letters, digits and spelled out
+/// digits. Every line has at least one digit, as part one needs.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut line = String::new();
+        for _ in 0..rng.between(1, 8) {
+            match rng.below(3) {
+                0 => line.push(char::from(b'1' + rng.below(9) as u8)),
+                1 => line.push_str(rng.choose::<&str>(&WORDS)),
+                _ => line.push(char::from(b'a' + rng.below(26) as u8)),
+            }
+        }
+        let at = rng.index(line.len() + 1);
+        line.insert(at, char::from(b'1' + rng.below(9) as u8));
+        input.push_str(&line);
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 142), (2, "-2", 281));
+
+    #[test]
+    fn test_extract_word_digits() {
+        let result = extract_word_digits("oneight");
+        assert_eq!(result, vec![1, 8]);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(200, &mut Rng::new(1));
+        assert_eq!(input, generate(200, &mut Rng::new(1)));
+        assert_eq!(input.lines().count(), 200);
+        assert!(part_one(&input).is_some());
+        assert!(part_two(&input).is_some());
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| !parse(input).is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+use std::cmp::max;
+use std::fmt::Write;
+
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(2);
+
+pub struct Game {
+    id: u32,
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+impl Game {
+    fn new(line: Span) -> Result<Self, ParseError> {
+        let (name, parts) = line.split_once(":")?;
+        let mut game = Game {
+            id: name.strip_prefix("Game ")?.parse()?,
+            red: 0,
+            green: 0,
+            blue: 0,
+        };
+
+        for round in parts.split(";") {
+            for die in round.split(",") {
+                let (count, color) = die.trim().split_once(" ")?;
+                let value = count.parse()?;
+                match color.as_str() {
+                    "red" => game.red = max(game.red, value),
+                    "green" => game.green = max(game.green, value),
+                    "blue" => game.blue = max(game.blue, value),
+                    _ => return Err(color.error(format!("unknown colour {:?}", color.as_str()))),
+                }
+            }
+        }
+        Ok(game)
+    }
+
+    fn is_legal(&self, red: u32, green: u32, blue: u32) -> bool {
+        self.red <= red && self.green <= green && self.blue <= blue
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    Span::new(input).lines().map(Game::new).collect()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let games = parse(input).reported()?;
+    let legal = games.iter().filter_map(|x| {
+        if x.is_legal(12, 13, 14) {
+            Some(x.id)
+        } else {
+            None
+        }
+    });
+    Some(legal.sum())
+}
+
+impl Game {
+    fn power(&self) -> u32 {
+        self.red * self.green * self.blue
+    }
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let games = parse(input).reported()?;
+    Some(games.iter().map(|g| g.power()).sum())
+}
+
+/// `size` random games, each showing a few handfuls of up to twenty cubes of a colour.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for id in 1..=size {
+        let mut handfuls = vec![];
+        for _ in 0..rng.between(1, 6) {
+            let mut colours = ["red", "green", "blue"];
+            rng.shuffle(&mut colours);
+            let mut cubes = vec![];
+            for colour in &colours[..rng.index(3) + 1] {
+                cubes.push(format!("{} {colour}", rng.between(1, 20)));
+            }
+            handfuls.push(cubes.join(", "));
+        }
+        writeln!(input, "Game {id}: {}", handfuls.join("; ")).unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 8), (2, "", 2286));
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, &mut Rng::new(2));
+        let games = parse(&input).unwrap();
+        assert_eq!(games.len(), 100);
+        assert!(games
+            .iter()
+            .all(|g| g.red <= 20 && g.green <= 20 && g.blue <= 20));
+        assert!(part_one(&input).is_some());
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
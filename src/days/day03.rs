@@ -0,0 +1,251 @@
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+use std::str::FromStr;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(3);
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Cell {
+    None,
+    Digit(u8),
+    Symbol(u8),
+}
+
+impl Cell {
+    fn new(v: u8) -> Option<Self> {
+        match v {
+            b'0'..=b'9' => Some(Cell::Digit(v - b'0')),
+            b'.' => Some(Cell::None),
+            _ if v.is_ascii_punctuation() => Some(Cell::Symbol(v)),
+            _ => None,
+        }
+    }
+}
+
+pub struct Schematic {
+    cells: Grid<Cell>,
+}
+
+impl FromStr for Schematic {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Schematic {
+            cells: Grid::try_parse(s, Cell::new)?,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Serial {
+    row: usize,
+    column: usize,
+    len: usize,
+    value: u32,
+}
+
+impl Serial {
+    fn is_used(&self, schematic: &Schematic) -> bool {
+        for r in (self.row as i32 - 1)..=(self.row as i32 + 1) {
+            for c in (self.column as i32 - 1)..=(self.column as i32 + self.len as i32) {
+                if let Cell::Symbol(_) = schematic.get_cell(r, c) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl Schematic {
+    fn find_serials(&self) -> Vec<Serial> {
+        let mut result = Vec::new();
+        for (r, row) in self.cells.rows().enumerate() {
+            let mut c: usize = 0;
+
+            for (digit, cells) in &row.iter().group_by(|a| matches!(a, Cell::Digit(_))) {
+                let found = cells.collect_vec();
+                if digit {
+                    result.push(Serial {
+                        row: r,
+                        column: c,
+                        len: found.len(),
+                        value: found.iter().fold(0, |acc: u32, c| match c {
+                            Cell::Digit(x) => acc * 10 + u32::from(*x),
+                            _ => acc,
+                        }),
+                    });
+                }
+                c += found.len();
+            }
+        }
+        result
+    }
+
+    fn get_cell(&self, r: i32, c: i32) -> Cell {
+        self.cells
+            .get_checked(r as isize, c as isize)
+            .copied()
+            .unwrap_or(Cell::None)
+    }
+
+    fn part_numbers(&self) -> u32 {
+        self.find_serials()
+            .iter()
+            .filter_map(|serial| {
+                if serial.is_used(self) {
+                    Some(serial.value)
+                } else {
+                    None
+                }
+            })
+            .sum()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Schematic, ParseError> {
+    input.parse()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let schematic = parse(input).reported()?;
+    Some(schematic.part_numbers())
+}
+
+impl Serial {
+    fn has_neighbour(&self, row: usize, col: usize) -> bool {
+        for r in (self.row as i32 - 1)..=(self.row as i32 + 1) {
+            for c in (self.column as i32 - 1)..=(self.column as i32 + self.len as i32) {
+                if r == row as i32 && c == col as i32 {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+struct Gear {
+    row: usize,
+    column: usize,
+}
+
+impl Schematic {
+    fn find_gears(&self) -> Vec<Gear> {
+        self.cells
+            .iter()
+            .filter(|(_, cell)| **cell == Cell::Symbol(b'*'))
+            .map(|((row, column), _)| Gear { row, column })
+            .collect()
+    }
+
+    fn gear_ratios(&self) -> u32 {
+        let mut result = 0;
+        let serials = self.find_serials();
+        let gears = self.find_gears();
+        for gear in gears {
+            let touching = serials
+                .iter()
+                .filter(|s| s.has_neighbour(gear.row, gear.column))
+                .collect_vec();
+            if touching.len() == 2 {
+                result += touching[0].value * touching[1].value;
+            }
+        }
+        result
+    }
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let schematic = parse(input).reported()?;
+    Some(schematic.gear_ratios())
+}
+
+/// A random `size` by `size` schematic, with part numbers and symbols scattered among the dots.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const SYMBOLS: &[u8] = b"*#+$/=&@%-";
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut row: Vec<u8> = Vec::with_capacity(size + 3);
+        while row.len() < size {
+            if !row.last().is_some_and(u8::is_ascii_digit) && rng.chance(0.15) {
+                row.extend(rng.between(1, 999).to_string().bytes());
+            } else if rng.chance(0.1) {
+                row.push(*rng.choose(SYMBOLS));
+            } else {
+                row.push(b'.');
+            }
+        }
+        row.truncate(size);
+        input.push_str(std::str::from_utf8(&row).unwrap());
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 4361), (2, "", 467_835));
+
+    #[test]
+    fn test_schematic_find_numbers() {
+        let example = crate::template::read_file("examples", DAY);
+        let schematic: Schematic = example.parse().unwrap();
+        let result = schematic.find_serials();
+        assert_eq!(result.len(), 10);
+        // println!("{:?}", result);
+    }
+
+    #[test]
+    fn test_schematic_cell() {
+        let example = crate::template::read_file("examples", DAY);
+        let schematic: Schematic = example.parse().unwrap();
+
+        assert_eq!(schematic.get_cell(0, 1), Cell::Digit(6));
+        assert_eq!(schematic.get_cell(-1, -1), Cell::None);
+        assert_eq!(schematic.get_cell(1, 3), Cell::Symbol(b'*'));
+    }
+
+    #[test]
+    fn test_serial_is_used() {
+        let example = crate::template::read_file("examples", DAY);
+        let schematic: Schematic = example.parse().unwrap();
+
+        let used = Serial {
+            row: 0,
+            column: 0,
+            len: 3,
+            value: 467,
+        };
+        assert!(used.is_used(&schematic));
+
+        let unused = Serial {
+            row: 0,
+            column: 5,
+            len: 3,
+            value: 114,
+        };
+        assert!(!unused.is_used(&schematic));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(50, &mut Rng::new(3));
+        let schematic: Schematic = input.parse().unwrap();
+        assert_eq!(schematic.cells.width(), 50);
+        assert_eq!(schematic.cells.height(), 50);
+        assert!(!schematic.find_serials().is_empty());
+        assert!(part_two(&input).is_some());
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(4);
+
+/// How many of the numbers we have are winning numbers, for each card.
+pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+    Span::new(input)
+        .lines()
+        .map(|line| {
+            let (_, card) = line.split_once(":")?;
+            let (winning, have) = card.split_once("|")?;
+            let check: HashSet<u32> = winning.numbers()?.into_iter().collect();
+            let have: Vec<u32> = have.numbers()?;
+            Ok(have.iter().filter(|m| check.contains(m)).count())
+        })
+        .collect()
+}
+
+fn score_card(wins: usize) -> u32 {
+    match wins {
+        0 => 0,
+        _ => 1 << (wins - 1),
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let wins = parse(input).reported()?;
+    Some(wins.into_iter().map(score_card).sum())
+}
+
+fn countup_cards(wins: &[usize]) -> usize {
+    let mut counts: Vec<usize> = wins.iter().map(|_| 1).collect();
+    for (i, &wins) in wins.iter().enumerate() {
+        for j in 0..wins {
+            counts[i + j + 1] += counts[i];
+        }
+    }
+    counts.iter().sum()
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let wins = parse(input).reported()?;
+    Some(countup_cards(&wins))
+}
+
+/// `size` random scratchcards, each with ten winning numbers and twenty-five that we have.
+/// Most cards win nothing, like the real ones, so the copies in part two don't run away,
+/// and no card wins copies of cards past the end of the table.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for card in 1..=size {
+        let mut numbers: Vec<u32> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let wins = if rng.chance(0.65) {
+            0
+        } else {
+            rng.index(4) + 1
+        };
+        let wins = wins.min(size - card);
+        let (winning, others) = numbers.split_at(10);
+        let mut have: Vec<u32> = winning[..wins].to_vec();
+        have.extend(&others[..25 - wins]);
+        rng.shuffle(&mut have);
+
+        let show = |numbers: &[u32]| numbers.iter().map(|n| format!("{n:>2}")).join(" ");
+        writeln!(input, "Card {card:>3}: {} | {}", show(winning), show(&have)).unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 13), (2, "", 30));
+
+    #[test]
+    fn test_score_card() {
+        let wins = parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
+        assert_eq!(score_card(wins[0]), 8);
+    }
+
+    #[test]
+    fn test_malformed_card() {
+        assert_eq!(part_one("Card 1: 41 48 | 83\nCard 2: 13 32 20"), None);
+        assert_eq!(
+            crate::template::runner::take_error().as_deref(),
+            Some(r#"line 2, column 8: expected "|" in " 13 32 20""#)
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(300, &mut Rng::new(4));
+        let wins = parse(&input).unwrap();
+        assert_eq!(wins.len(), 300);
+        assert_eq!(wins.last(), Some(&0));
+        assert!(part_two(&input).is_some());
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
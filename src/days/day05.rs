@@ -0,0 +1,161 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::ranges::{RangeMap, RangeSet};
+use crate::template::runner::Reported;
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(5);
+
+#[derive(Default, Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    steps: Vec<RangeMap<u64>>,
+}
+
+impl FromStr for Almanac {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut blocks = Span::new(s).blocks();
+        // There is always a first block, even if it's empty
+        let seeds = blocks.next().unwrap_or(Span::new(s));
+        let mut almanac = Self {
+            seeds: seeds.strip_prefix("seeds:")?.numbers()?,
+            steps: Vec::new(),
+        };
+
+        for block in blocks {
+            let mut lines = block.lines();
+            if let Some(header) = lines.next() {
+                header.strip_suffix(" map:")?.split_once("-to-")?;
+            }
+            let mut step = RangeMap::new();
+            for line in lines {
+                let [destination, source, length]: [u64; 3] = line.array(line.numbers()?)?;
+                let too_big = || line.error("range goes past the largest number");
+                let end = source.checked_add(length).ok_or_else(too_big)?;
+                destination.checked_add(length).ok_or_else(too_big)?;
+                step.insert(source..end, destination);
+            }
+            almanac.steps.push(step);
+        }
+        Ok(almanac)
+    }
+}
+
+impl Almanac {
+    fn map(&self, seed: u64) -> u64 {
+        self.steps.iter().fold(seed, |acc, m| m.get(acc))
+    }
+
+    fn lowest_location(&self) -> u64 {
+        self.seeds.iter().map(|seed| self.map(*seed)).min().unwrap()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Almanac, ParseError> {
+    input.parse()
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let almanac = parse(input).reported()?;
+    Some(almanac.lowest_location())
+}
+
+impl Almanac {
+    fn lowest_ranged_location(&self) -> u64 {
+        let seeds: RangeSet<u64> = self
+            .seeds
+            .iter()
+            .tuples()
+            .map(|(&start, &count)| start..start.saturating_add(count))
+            .collect();
+        self.steps
+            .iter()
+            .fold(seeds, |acc, m| m.map(&acc))
+            .min()
+            .unwrap()
+    }
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let almanac = parse(input).reported()?;
+    Some(almanac.lowest_ranged_location())
+}
+
+/// A random almanac with `size` ranges of seeds, and up to `size` ranges in each of its seven
+/// maps. The numbers stay below 2^32, like the real ones.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const LIMIT: i64 = 1 << 32;
+    const STAGES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+    let size = size.max(1);
+    let mut seeds = vec![];
+    for _ in 0..size {
+        let start = rng.between(0, LIMIT - 1);
+        let count = rng.between(1, (LIMIT - start).min(LIMIT / size as i64));
+        seeds.push(format!("{start} {count}"));
+    }
+
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+    for (from, to) in STAGES.iter().tuple_windows() {
+        writeln!(input, "\n{from}-to-{to} map:").unwrap();
+        // Ranges between pairs of cut points, leaving the gaps between pairs unmapped
+        let mut cuts: Vec<i64> = (0..size * 2).map(|_| rng.between(0, LIMIT)).collect();
+        cuts.sort_unstable();
+        for (&start, &end) in cuts.iter().tuples() {
+            if start < end {
+                let length = end - start;
+                let destination = rng.between(0, LIMIT - length);
+                writeln!(input, "{destination} {start} {length}").unwrap();
+            }
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 35), (2, "", 46));
+
+    #[test]
+    fn test_mapping_apply() {
+        let almanac: Almanac = crate::template::read_file("examples", DAY).parse().unwrap();
+        assert_eq!(almanac.steps[0].get(98), 50);
+        assert_eq!(almanac.steps[0].get(99), 51);
+        assert_eq!(almanac.steps[0].get(53), 55);
+        assert_eq!(almanac.steps[0].get(10), 10);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(20, &mut Rng::new(5));
+        let almanac = parse(&input).unwrap();
+        assert_eq!(almanac.seeds.len(), 40);
+        assert_eq!(almanac.steps.len(), 7);
+        let lowest = part_two(&input).unwrap();
+        assert!(lowest < 1 << 32);
+        for &start in almanac.seeds.iter().step_by(2) {
+            assert!(almanac.map(start) >= lowest);
+        }
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
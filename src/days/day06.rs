@@ -0,0 +1,186 @@
+use std::fmt::Write;
+use std::ops::Range;
+
+use crate::numeric::isqrt;
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(6);
+
+#[derive(Debug, Clone)]
+pub struct Race {
+    time: u64,
+    distance: u64,
+}
+
+impl Race {
+    /// The times to hold the button for that beat the record. Holding for `start` travels
+    /// `start * (time - start)`, so these lie between the roots of a quadratic.
+    fn winning_starts(&self) -> Range<u64> {
+        let (time, distance) = (u128::from(self.time), u128::from(self.distance));
+        let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+            return 0..0;
+        };
+        // Rounding the root may leave us short, so walk up to the first winner
+        let mut low = (time - isqrt(discriminant)) / 2;
+        while low <= time / 2 && low * (time - low) <= distance {
+            low += 1;
+        }
+        if low > time / 2 {
+            return 0..0;
+        }
+        // Winners are symmetric about half the time
+        low as u64..(time - low + 1) as u64
+    }
+
+    fn ways_to_win(&self) -> u64 {
+        let starts = self.winning_starts();
+        starts.end - starts.start
+    }
+}
+
+/// The numbers after the `Time:` and `Distance:` labels.
+fn fields(s: &str) -> Result<[Span<'_>; 2], ParseError> {
+    let input = Span::new(s);
+    let [times, distances] = input.array(input.lines())?;
+    Ok([
+        times.strip_prefix("Time:")?,
+        distances.strip_prefix("Distance:")?,
+    ])
+}
+
+pub fn parse(s: &str) -> Result<Vec<Race>, ParseError> {
+    let [times, distances] = fields(s)?;
+    Ok(times
+        .numbers()?
+        .into_iter()
+        .zip(distances.numbers()?)
+        .map(|(time, distance)| Race { time, distance })
+        .collect())
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let races = parse(input).reported()?;
+    Some(races.iter().map(Race::ways_to_win).product())
+}
+
+/// The numbers with the spaces between their digits taken out.
+fn parse2(s: &str) -> Result<Race, ParseError> {
+    let [time, distance] = fields(s)?.map(|span| {
+        span.as_str()
+            .bytes()
+            .filter(u8::is_ascii_digit)
+            .fold(0, |acc, d| acc * 10 + u64::from(d - b'0'))
+    });
+    Ok(Race { time, distance })
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let race = parse2(input).reported()?;
+    Some(race.ways_to_win())
+}
+
+/// A random sheet of `size` races, which all have a record that can be beaten. Part two
+/// reads the digits of all the times as one number, so there are never more than nine races
+/// and they share nine digits of time between them.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let races = size.clamp(1, 9);
+    let digits = (9 / races) as u32;
+    let (mut times, mut distances) = (String::new(), String::new());
+    for _ in 0..races {
+        let time = rng.between(10_i64.pow(digits - 1).max(4), 10_i64.pow(digits) - 1);
+        let held = rng.between(1, time / 2 - 1);
+        let distance = held * (time - held);
+        let width = distance.to_string().len().max(digits as usize) + 2;
+        write!(times, "{time:>width$}").unwrap();
+        write!(distances, "{distance:>width$}").unwrap();
+    }
+    format!("{:<9}{times}\n{:<9}{distances}\n", "Time:", "Distance:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{agree, Arbitrary, Config};
+
+    crate::example_tests!((1, "", 288), (2, "", 71_503));
+
+    #[test]
+    fn test_race_winning_starts() {
+        let race = Race {
+            time: 7,
+            distance: 9,
+        };
+        assert_eq!(race.winning_starts(), 2..6);
+    }
+
+    #[test]
+    fn test_race_ties_do_not_win() {
+        let race = Race {
+            time: 30,
+            distance: 200,
+        };
+        assert_eq!(race.winning_starts(), 11..20);
+        let race = Race {
+            time: 4,
+            distance: 4,
+        };
+        assert_eq!(race.ways_to_win(), 0);
+    }
+
+    #[test]
+    fn test_generate() {
+        for size in [1, 3, 4, 9, 20] {
+            let input = generate(size, &mut Rng::new(6));
+            let races = parse(&input).unwrap();
+            assert_eq!(races.len(), size.min(9));
+            assert!(part_one(&input).unwrap() > 0);
+            assert!(part_two(&input).unwrap() > 0);
+        }
+    }
+
+    impl Arbitrary for Race {
+        fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+            let time = rng.below((size * size) as u64 + 1);
+            // Half the records are exactly a distance some hold reaches, to try the ties
+            let distance = if rng.chance(0.5) {
+                let hold = rng.below(time + 1);
+                hold * (time - hold)
+            } else {
+                rng.below(time * time / 4 + 2)
+            };
+            Self { time, distance }
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            let (time, distance) = (self.time, self.distance);
+            let smaller = [
+                (time / 2, distance),
+                (time.saturating_sub(1), distance),
+                (time, 0),
+                (time, distance / 2),
+                (time, distance.saturating_sub(1)),
+            ];
+            smaller
+                .into_iter()
+                .filter(|&smaller| smaller != (time, distance))
+                .map(|(time, distance)| Self { time, distance })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_ways_to_win_match_brute_force() {
+        Config::from_env().max_size(60).check(|race: &Race| {
+            let holds = (0..=race.time).filter(|hold| hold * (race.time - hold) > race.distance);
+            agree(holds.count() as u64, race.ways_to_win())
+        });
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
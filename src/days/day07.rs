@@ -0,0 +1,247 @@
+use std::fmt::Write;
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(7);
+
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord)]
+enum Rank {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+#[derive(Debug, PartialEq, PartialOrd)]
+enum Value {
+    Joker,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Value {
+    fn new(value: u8) -> Option<Self> {
+        Some(match value {
+            b'A' => Self::Ace,
+            b'K' => Self::King,
+            b'Q' => Self::Queen,
+            b'J' => Self::Jack,
+            b'T' => Self::Ten,
+            b'9' => Self::Nine,
+            b'8' => Self::Eight,
+            b'7' => Self::Seven,
+            b'6' => Self::Six,
+            b'5' => Self::Five,
+            b'4' => Self::Four,
+            b'3' => Self::Three,
+            b'2' => Self::Two,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Hand {
+    cards: Vec<Value>,
+    rank: Rank,
+    jokers_wild: bool,
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match self.rank.partial_cmp(&other.rank) {
+            Some(Ordering::Equal) => self.cards.partial_cmp(&other.cards),
+            rank => rank,
+        }
+    }
+}
+
+impl Hand {
+    fn new(hand: Span, jokers_wild: bool) -> Result<Self, ParseError> {
+        let s = hand.as_str();
+        if s.len() != 5 {
+            return Err(hand.error(format!("expected 5 cards, found {s:?}")));
+        }
+        let cards = s
+            .char_indices()
+            .map(|(i, c)| {
+                let card = u8::try_from(c)
+                    .ok()
+                    .and_then(Value::new)
+                    .ok_or_else(|| hand.skip(i).error(format!("{c:?} isn't a card")))?;
+                Ok(if jokers_wild && card == Value::Jack {
+                    Value::Joker
+                } else {
+                    card
+                })
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        let mut histo = HashMap::new();
+        for b in s.as_bytes() {
+            histo.entry(b).and_modify(|x| *x += 1).or_insert(1);
+        }
+        if jokers_wild {
+            if let Some((_, num_jokers)) = histo.remove_entry(&b'J') {
+                if num_jokers == 5 {
+                    // oops, put that back
+                    histo.insert(&b'J', 5);
+                } else {
+                    let largest = histo.iter().max_by_key(|e| e.1).unwrap().0;
+                    histo.entry(largest).and_modify(|x| *x += num_jokers);
+                }
+            };
+        }
+        let counts: Vec<_> = histo.values().sorted().rev().collect();
+        Ok(Hand {
+            cards,
+            rank: match counts.as_slice() {
+                [5] => Rank::FiveOfAKind,
+                [4, 1] => Rank::FourOfAKind,
+                [3, 2] => Rank::FullHouse,
+                [3, 1, 1] => Rank::ThreeOfAKind,
+                [2, 2, 1] => Rank::TwoPair,
+                [2, 1, 1, 1] => Rank::OnePair,
+                _ => Rank::HighCard,
+            },
+            jokers_wild,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Camel {
+    hands: Vec<(Hand, usize)>,
+}
+
+impl Camel {
+    fn new(s: &str, jokers_wild: bool) -> Result<Self, ParseError> {
+        let mut game = Self::default();
+        for l in Span::new(s).lines() {
+            let (cards, bet) = l.split_once(" ")?;
+            let hand: Hand = Hand::new(cards, jokers_wild)?;
+            game.hands.push((hand, bet.parse()?));
+        }
+        Ok(game)
+    }
+
+    fn total_winnings(&self) -> usize {
+        let mut winnings = 0;
+        for (rank, (_hand, bid)) in self
+            .hands
+            .iter()
+            .sorted_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+            .enumerate()
+        {
+            winnings += (rank + 1) * bid;
+        }
+        winnings
+    }
+}
+
+/// The hands and their bids, with jokers as the weakest cards when `jokers_wild`.
+pub fn parse(input: &str, jokers_wild: bool) -> Result<Camel, ParseError> {
+    Camel::new(input, jokers_wild)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let game = parse(input, false).reported()?;
+    Some(game.total_winnings())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let game = parse(input, true).reported()?;
+    Some(game.total_winnings())
+}
+
+/// `size` random hands of camel cards, with bids up to a thousand.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const CARDS: &[u8] = b"AKQJT98765432";
+    let mut input = String::new();
+    for _ in 0..size {
+        let hand: String = (0..5).map(|_| char::from(*rng.choose(CARDS))).collect();
+        writeln!(input, "{hand} {}", rng.between(1, 1000)).unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+    use test_case::test_case;
+
+    crate::example_tests!((1, "", 6440), (2, "", 5905));
+
+    #[test]
+    fn test_hand_fromstr() {
+        let hand: Hand = Hand::new(Span::new("AAAAA"), false).unwrap();
+        assert_eq!(
+            hand,
+            Hand {
+                cards: vec![Value::Ace, Value::Ace, Value::Ace, Value::Ace, Value::Ace],
+                rank: Rank::FiveOfAKind,
+                jokers_wild: false,
+            }
+        );
+    }
+
+    #[test_case("AAAAA", false, Rank::FiveOfAKind)]
+    #[test_case("AAJAA", false, Rank::FourOfAKind)]
+    #[test_case("AAJ3A", false, Rank::ThreeOfAKind)]
+    #[test_case("AAAJJ", false, Rank::FullHouse)]
+    #[test_case("AA22J", false, Rank::TwoPair)]
+    #[test_case("AA32J", false, Rank::OnePair)]
+    #[test_case("2345J", false, Rank::HighCard)]
+    #[test_case("AAAAA", true, Rank::FiveOfAKind)]
+    #[test_case("AAJAA", true, Rank::FiveOfAKind)]
+    #[test_case("AAJ3A", true, Rank::FourOfAKind)]
+    #[test_case("AAAJJ", true, Rank::FiveOfAKind)]
+    #[test_case("AA22J", true, Rank::FullHouse)]
+    #[test_case("AA32J", true, Rank::ThreeOfAKind)]
+    #[test_case("2345J", true, Rank::OnePair)]
+    fn test_hand_kind(hand: &str, jokers_wild: bool, kind: Rank) {
+        let result = Hand::new(Span::new(hand), jokers_wild).unwrap();
+        assert_eq!(result.rank, kind);
+    }
+
+    #[test_case("AAAAA", "22222")]
+    #[test_case("AAAAA", "23456")]
+    fn test_hand_beats(hand1: &str, hand2: &str) {
+        let hand1 = Hand::new(Span::new(hand1), false).unwrap();
+        let hand2 = Hand::new(Span::new(hand2), false).unwrap();
+        check!(hand1 > hand2);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1000, &mut Rng::new(7));
+        assert_eq!(parse(&input, false).unwrap().hands.len(), 1000);
+        assert!(part_one(&input).is_some());
+        assert!(part_two(&input).is_some());
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input, true).is_ok());
+    }
+}
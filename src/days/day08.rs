@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use crate::cycles::{self, Periodic};
+use crate::numeric::is_prime;
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(8);
+
+#[derive(Default, Debug)]
+pub struct Map {
+    path: Vec<u8>,
+    map: HashMap<[u8; 3], ([u8; 3], [u8; 3])>,
+}
+
+impl Map {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        let (path, nodes) = Span::new(s).split_once("\n\n")?;
+        let path = path.trim();
+        if let Some(at) = path.as_str().find(|c| c != 'L' && c != 'R') {
+            return Err(path.skip(at).error("expected only L and R in the path"));
+        }
+        if path.as_str().is_empty() {
+            return Err(path.error("expected a path"));
+        }
+
+        let mut map = Self {
+            path: path.as_str().bytes().collect(),
+            ..Self::default()
+        };
+        for line in nodes.lines() {
+            let (node, next) = line.split_once(" = ")?;
+            let (left, right) = next
+                .strip_prefix("(")?
+                .strip_suffix(")")?
+                .split_once(", ")?;
+            map.map
+                .insert(Self::node(node)?, (Self::node(left)?, Self::node(right)?));
+        }
+        Ok(map)
+    }
+
+    fn node(name: Span) -> Result<[u8; 3], ParseError> {
+        name.as_str().as_bytes().try_into().map_err(|_| {
+            name.error(format!(
+                "expected a 3 letter node, found {:?}",
+                name.as_str()
+            ))
+        })
+    }
+
+    fn steps(&self) -> usize {
+        let mut current = b"AAA";
+        let mut steps = 0;
+        loop {
+            let direction = self.path[steps % self.path.len()];
+            let Some((l, r)) = self.map.get(current) else {
+                unreachable!("bad step {:?}", current);
+            };
+            if direction == b'L' {
+                current = l;
+            } else {
+                current = r;
+            }
+            steps += 1;
+
+            if current == b"ZZZ" {
+                return steps;
+            }
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Map, ParseError> {
+    Map::new(input)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let map = parse(input).reported()?;
+    Some(map.steps())
+}
+
+impl Map {
+    /// The times a ghost starting at `start` stands on a `..Z` node, once it's going round in
+    /// circles.  Visits to `..Z` nodes on the way into the loop are never repeated, so ignored.
+    fn loops(&self, start: &[u8; 3]) -> Vec<Periodic> {
+        let (cycle, history) = cycles::find_cycle((start, 0), |&(current, i)| {
+            let Some((l, r)) = self.map.get(current) else {
+                unreachable!("bad step {:?}", current);
+            };
+            let next = if self.path[i] == b'L' { l } else { r };
+            (next, (i + 1) % self.path.len())
+        });
+
+        history
+            .iter()
+            .enumerate()
+            .skip(cycle.prefix)
+            .filter(|(_, (node, _))| node[2] == b'Z')
+            .map(|(steps, _)| Periodic::new(steps, cycle.period))
+            .collect()
+    }
+
+    fn parallel_steps(&self) -> usize {
+        self.map
+            .keys()
+            .filter(|k| k[2] == b'A')
+            .map(|g| self.loops(g))
+            .reduce(|arrivals, ghost| {
+                arrivals
+                    .iter()
+                    .cartesian_product(&ghost)
+                    .filter_map(|(a, b)| a.combine(*b))
+                    .collect()
+            })
+            .unwrap()
+            .iter()
+            .map(|arrival| arrival.offset)
+            .min()
+            .unwrap()
+    }
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let map = parse(input).reported()?;
+    Some(map.parallel_steps())
+}
+
+/// A random map shaped like the real ones, with about `size` nodes. Each ghost follows its
+/// own loop, whose length is a whole number of passes through the instructions, and reaches
+/// its `Z` node once per lap. The laps are different primes, so the ghosts meet up rarely.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const LAPS: [usize; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+    let mut laps = LAPS;
+    rng.shuffle(&mut laps);
+    let laps = &laps[..6];
+
+    // Leave room for the loops among the names that don't end in A or Z
+    let total: usize = laps.iter().sum();
+    let most = (size / total).clamp(2, 26 * 26 * 24 / total);
+    let length = (2..=most).rev().find(|&n| is_prime(n as u64)).unwrap();
+    let path: Vec<char> = (0..length)
+        .map(|_| if rng.chance(0.5) { 'L' } else { 'R' })
+        .collect();
+
+    let letters = b'A'..=b'Z';
+    let mut names: Vec<String> = letters
+        .clone()
+        .cartesian_product(letters.clone())
+        .cartesian_product(b'B'..=b'Y')
+        .map(|((a, b), c)| String::from_utf8(vec![a, b, c]).unwrap())
+        .collect();
+    rng.shuffle(&mut names);
+    let mut prefixes: Vec<String> = letters
+        .clone()
+        .cartesian_product(letters)
+        .map(|(a, b)| String::from_utf8(vec![a, b]).unwrap())
+        .filter(|prefix| prefix != "AA" && prefix != "ZZ")
+        .collect();
+    rng.shuffle(&mut prefixes);
+
+    let mut lines = vec![];
+    for (ghost, &lap) in laps.iter().enumerate() {
+        let (start, end) = if ghost == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (
+                format!("{}A", prefixes[ghost]),
+                format!("{}Z", prefixes[ghost]),
+            )
+        };
+        // The end leads round to the first step of the loop again, just like the start
+        let mut ring: Vec<String> = names.split_off(names.len() - (lap * length - 1));
+        ring.insert(0, end);
+        for (position, node) in ring.iter().enumerate() {
+            let next = &ring[(position + 1) % ring.len()];
+            let other = rng.choose(&ring);
+            let (left, right) = if path[position % length] == 'L' {
+                (next, other)
+            } else {
+                (other, next)
+            };
+            lines.push(format!("{node} = ({left}, {right})"));
+            if position == 0 {
+                lines.push(format!("{start} = ({left}, {right})"));
+            }
+        }
+    }
+    rng.shuffle(&mut lines);
+
+    format!(
+        "{}\n\n{}\n",
+        path.iter().collect::<String>(),
+        lines.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 2), (1, "-2", 6), (2, "-3", 6));
+
+    #[test]
+    fn test_generate() {
+        let input = generate(2000, &mut Rng::new(8));
+        let map = parse(&input).unwrap();
+        assert!(map.map.contains_key(b"AAA") && map.map.contains_key(b"ZZZ"));
+        assert_eq!(map.map.keys().filter(|k| k[2] == b'A').count(), 6);
+        let steps = part_one(&input).unwrap();
+        assert_eq!(steps % map.path.len(), 0);
+        assert_eq!(part_two(&input).unwrap() % steps, 0);
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
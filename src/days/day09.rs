@@ -0,0 +1,100 @@
+use crate::numeric::extrapolate;
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+use std::fmt::Write;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(9);
+
+fn readings(line: Span) -> Result<Vec<i128>, ParseError> {
+    line.split(" ").map(|v| v.parse()).collect()
+}
+
+/// The history of each value in the report.
+pub fn parse(input: &str) -> Result<Vec<Vec<i128>>, ParseError> {
+    Span::new(input).lines().map(readings).collect()
+}
+
+/// The reading after the last, or `None` if it doesn't fit in an `i64`.
+fn next_number(values: &[i128]) -> Option<i64> {
+    extrapolate(values, values.len() as i128)?.try_into().ok()
+}
+
+pub fn part_one(input: &str) -> Option<i64> {
+    parse(input)
+        .reported()?
+        .iter()
+        .map(|values| next_number(values))
+        .sum()
+}
+
+/// The reading before the first, or `None` if it doesn't fit in an `i64`.
+fn prev_number(values: &[i128]) -> Option<i64> {
+    extrapolate(values, -1)?.try_into().ok()
+}
+
+pub fn part_two(input: &str) -> Option<i64> {
+    parse(input)
+        .reported()?
+        .iter()
+        .map(|values| prev_number(values))
+        .sum()
+}
+
+/// `size` random sequences of twenty-one readings, each following a polynomial of degree up
+/// to ten, like the real ones.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        // The first differences of every order, the last of which stays the same
+        let degree = rng.index(11);
+        let mut differences: Vec<i64> = (0..=degree).map(|_| rng.between(-9, 9)).collect();
+        let mut readings = vec![];
+        for _ in 0..21 {
+            readings.push(differences[0]);
+            for order in 0..degree {
+                differences[order] += differences[order + 1];
+            }
+        }
+        writeln!(input, "{}", readings.iter().join(" ")).unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+    use test_case::test_case;
+
+    crate::example_tests!((1, "", 114), (2, "", 2));
+
+    #[test_case("0 3 6 9 12 15", 18)]
+    #[test_case("10 13 16 21 30 45", 68)]
+    fn test_next_number(s: &str, n: i64) {
+        check!(next_number(&readings(Span::new(s)).unwrap()) == Some(n));
+    }
+
+    #[test_case("0 3 6 9 12 15", -3)]
+    #[test_case("10 13 16 21 30 45", 5)]
+    fn test_prev_number(s: &str, n: i64) {
+        check!(prev_number(&readings(Span::new(s)).unwrap()) == Some(n));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, &mut Rng::new(9));
+        let sequences = parse(&input).unwrap();
+        assert_eq!(sequences.len(), 100);
+        assert!(sequences.iter().all(|s| s.len() == 21));
+        assert!(part_one(&input).is_some());
+        assert!(part_two(&input).is_some());
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
@@ -0,0 +1,484 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::grid::Pos;
+use crate::image::{Image, WHITE};
+use crate::parse::{ParseError, Span};
+use crate::polygon::Polygon;
+use crate::random::{random_loop, Rng};
+use crate::template::runner::Reported;
+use crate::visualize::{Colour, Frame};
+use pathfinding::directed::bfs::bfs_loop;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(10);
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Tile {
+    Empty,      // .
+    Vertical,   // |
+    Horizontal, // -
+    CornerL,    // L
+    CornerJ,    // J
+    Corner7,    // 7,
+    CornerF,    // F
+    Start,      // S
+}
+
+impl Tile {
+    fn new(c: u8) -> Option<Self> {
+        Some(match c {
+            b'.' => Self::Empty,
+            b'|' => Self::Vertical,
+            b'-' => Self::Horizontal,
+            b'L' => Self::CornerL,
+            b'J' => Self::CornerJ,
+            b'7' => Self::Corner7,
+            b'F' => Self::CornerF,
+            b'S' => Self::Start,
+            _ => return None,
+        })
+    }
+
+    fn symbol(self) -> char {
+        match self {
+            Self::Empty => ' ',
+            Self::Vertical => '│',
+            Self::Horizontal => '─',
+            Self::CornerL => '└',
+            Self::CornerJ => '┘',
+            Self::Corner7 => '┐',
+            Self::CornerF => '┌',
+            Self::Start => 'S',
+        }
+    }
+
+    /// The offsets of the neighbours the tile's pipe joins up with.
+    fn arms(self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Vertical => &[(-1, 0), (1, 0)],
+            Self::Horizontal => &[(0, -1), (0, 1)],
+            Self::CornerL => &[(-1, 0), (0, 1)],
+            Self::CornerJ => &[(-1, 0), (0, -1)],
+            Self::Corner7 => &[(1, 0), (0, -1)],
+            Self::CornerF => &[(1, 0), (0, 1)],
+            Self::Empty | Self::Start => &[],
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct Maze {
+    start: Point<i32>,
+    cells: Grid<Tile>,
+    /// The loop through the start, with the way it heads out of each cell. Empty if the
+    /// start's pipe doesn't loop back round to it.
+    path: Vec<(Point<i32>, Direction)>,
+}
+
+/// The pipes drawn with box-drawing characters, and the start drawn as the pipe under it.
+impl Display for Maze {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.rows() {
+            for &tile in row {
+                match tile {
+                    Tile::Empty => write!(f, ".")?,
+                    tile => write!(f, "{}", tile.symbol())?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Maze {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        let cells = Grid::try_parse(s, Tile::new)?;
+        let (r, c) = cells
+            .find(|&tile| tile == Tile::Start)
+            .ok_or_else(|| Span::new(s).error("expected a start, S"))?;
+
+        let mut maze = Self {
+            start: Point::new(r as i32, c as i32),
+            cells,
+            path: Vec::new(),
+        };
+        maze.cells[(r, c)] = maze
+            .start_tile()
+            .ok_or_else(|| maze.error_at_start("can't tell which pipe is under the start"))?;
+        maze.path = maze.find_loop().unwrap_or_default();
+        Ok(maze)
+    }
+
+    fn error_at_start(&self, message: &str) -> ParseError {
+        ParseError {
+            line: self.start.row as usize + 1,
+            column: self.start.column as usize + 1,
+            message: message.to_string(),
+        }
+    }
+
+    fn get(&self, position: Point<i32>) -> Tile {
+        self.cells
+            .get_checked(position.row as isize, position.column as isize)
+            .copied()
+            .unwrap_or(Tile::Empty)
+    }
+
+    /// The pipe under the start, from which of its neighbours have a pipe pointing back at it.
+    fn start_tile(&self) -> Option<Tile> {
+        let connects = |direction: Direction| {
+            let (dr, dc) = direction.delta();
+            self.get(self.start.step(direction))
+                .arms()
+                .contains(&(-dr, -dc))
+        };
+        let [north, south, west, east] = [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ]
+        .map(connects);
+
+        match (north, south, west, east) {
+            (true, true, _, _) => Some(Tile::Vertical),
+            (_, _, true, true) => Some(Tile::Horizontal),
+            (true, _, _, true) => Some(Tile::CornerL),
+            (true, _, true, _) => Some(Tile::CornerJ),
+            (_, true, true, _) => Some(Tile::Corner7),
+            (_, true, _, true) => Some(Tile::CornerF),
+            _ => None,
+        }
+    }
+
+    /// Where the pipe at `position` leads, or `None` if it doesn't join up with the way we came.
+    fn next_step(
+        &self,
+        position: Point<i32>,
+        facing: Direction,
+    ) -> Option<(Point<i32>, Direction)> {
+        let tile = self.get(position);
+
+        let heading = match (tile, &facing) {
+            // keeps on going
+            (Tile::Vertical, Direction::South) => Direction::South,
+            (Tile::Vertical, Direction::North) => Direction::North,
+            (Tile::Horizontal, Direction::West) => Direction::West,
+            (Tile::Horizontal, Direction::East) => Direction::East,
+
+            // approaches from other direction, turns
+            (Tile::CornerL, Direction::South) => Direction::East,
+            (Tile::CornerL, Direction::West) => Direction::North,
+
+            (Tile::CornerJ, Direction::South) => Direction::West,
+            (Tile::CornerJ, Direction::East) => Direction::North,
+
+            (Tile::CornerF, Direction::North) => Direction::East,
+            (Tile::CornerF, Direction::West) => Direction::South,
+
+            (Tile::Corner7, Direction::North) => Direction::West,
+            (Tile::Corner7, Direction::East) => Direction::South,
+
+            _ => return None,
+        };
+
+        // return our new position and facing
+        Some((position.step(heading), heading))
+    }
+}
+
+impl Maze {
+    fn find_loop(&self) -> Option<Vec<(Point<i32>, Direction)>> {
+        let direction = match self.get(self.start) {
+            Tile::Vertical => Direction::North,
+            Tile::CornerL => Direction::South,
+            Tile::CornerJ => Direction::East,
+            Tile::Horizontal => Direction::East,
+            Tile::Corner7 => Direction::North,
+            Tile::CornerF => Direction::North,
+            Tile::Empty | Tile::Start => return None,
+        };
+        bfs_loop(&(self.start, direction), |&(position, facing)| {
+            self.next_step(position, facing)
+        })
+    }
+
+    fn looping_path(&self) -> &[(Point<i32>, Direction)] {
+        &self.path
+    }
+
+    /// The cells of the loop in order, starting and ending at the start.
+    fn loop_cells(&self) -> Vec<Pos> {
+        self.looping_path()
+            .iter()
+            .map(|&(position, _)| (position.row as usize, position.column as usize))
+            .collect()
+    }
+
+    fn furthest_loop_distance(&self) -> usize {
+        let path = self.looping_path();
+        path.len() / 2
+    }
+}
+
+pub fn parse(input: &str) -> Result<Maze, ParseError> {
+    let maze = Maze::new(input)?;
+    if maze.path.is_empty() {
+        return Err(maze.error_at_start("the pipe from the start doesn't loop back to it"));
+    }
+    Ok(maze)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let maze = parse(input).reported()?;
+    Some(maze.furthest_loop_distance())
+}
+
+impl Maze {
+    fn contained_cells(&self) -> usize {
+        // The loop runs through the middle of its cells, so the contained cells are the
+        // lattice points strictly inside it
+        let path = Polygon::new(
+            self.looping_path()
+                .iter()
+                .map(|&(position, _)| Point::new(position.row.into(), position.column.into()))
+                .collect(),
+        );
+        path.interior_points() as usize
+    }
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let maze = parse(input).reported()?;
+    Some(maze.contained_cells())
+}
+
+/// Part one follows the loop out both ways from the start until they meet, part two
+/// sweeps down the maze marking the cells the loop contains.
+pub fn visualize(input: &str, part: u8) -> Vec<Frame> {
+    let Some(maze) = parse(input).reported() else {
+        return Vec::new();
+    };
+    let path = maze.loop_cells();
+    let mut frame = Frame::from_grid(&maze.cells, |&tile| tile.symbol());
+    for (pos, _) in maze.cells.iter() {
+        frame.paint(pos, Colour::Grey);
+    }
+
+    let mut frames = Vec::new();
+    if part == 1 {
+        // The path starts and ends at the start, so go back along one end and out along the other
+        let furthest = path.len() / 2;
+        let stride = furthest.div_ceil(50).max(1);
+        for steps in (0..furthest).step_by(stride).chain([furthest]) {
+            let both_ways: Vec<Pos> = path[path.len() - 1 - steps..]
+                .iter()
+                .chain(&path[1..=steps])
+                .copied()
+                .collect();
+            frame.draw_path(&both_ways, Colour::Cyan);
+            frames.push(frame.clone().with_caption(format!("{steps} steps")));
+        }
+        return frames;
+    }
+
+    frame.draw_path(&path, Colour::Cyan);
+    let polygon = outline(&path);
+    let mut contained = 0;
+    for row in 0..maze.cells.height() {
+        for column in 0..maze.cells.width() {
+            if polygon.contains(Point::new(row as i64, column as i64)) {
+                frame.draw((row, column), '●', Some(Colour::Green));
+                contained += 1;
+            }
+        }
+        frames.push(frame.clone().with_caption(format!("{contained} contained")));
+    }
+    frames
+}
+
+fn outline(path: &[Pos]) -> Polygon {
+    Polygon::new(
+        path.iter()
+            .map(|&(row, column)| Point::new(row as i64, column as i64))
+            .collect(),
+    )
+}
+
+/// The loop in white with the cells it contains shaded green, and the other pipes in grey.
+pub fn render(input: &str) -> Vec<Image> {
+    const SCALE: usize = 3;
+    let Some(maze) = parse(input).reported() else {
+        return Vec::new();
+    };
+    let path = maze.loop_cells();
+    let on_loop: HashSet<Pos> = path.iter().copied().collect();
+    let polygon = outline(&path);
+
+    let (width, height) = (maze.cells.width(), maze.cells.height());
+    let mut image = Image::new(width * SCALE, height * SCALE, [16, 16, 32]);
+    for ((row, column), &tile) in maze.cells.iter() {
+        let colour = if on_loop.contains(&(row, column)) {
+            WHITE
+        } else if polygon.contains(Point::new(row as i64, column as i64)) {
+            image.fill((row * SCALE, column * SCALE), (SCALE, SCALE), [40, 160, 60]);
+            continue;
+        } else {
+            [80, 80, 80]
+        };
+        if tile == Tile::Empty {
+            continue;
+        }
+        // Each pipe runs through the middle of its cell, out to the edges it joins
+        let middle = (row * SCALE + 1, column * SCALE + 1);
+        image.set(middle, colour);
+        for &(dr, dc) in tile.arms() {
+            image.set(
+                (
+                    middle.0.wrapping_add_signed(dr),
+                    middle.1.wrapping_add_signed(dc),
+                ),
+                colour,
+            );
+        }
+    }
+    vec![image]
+}
+
+/// A random field of about `size` by `size` pipes, with one big loop through it and junk
+/// pipes everywhere else. Some of the junk always ends up inside the loop, and only the
+/// loop's own pipes point at the start.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let quarter = (size / 4).max(1);
+    let side = quarter * 4;
+    // Doubled in size, the loop leaves room between its pipes for tiles to be enclosed
+    let path = random_loop(rng, quarter, quarter, 0.6);
+    let path: Vec<Pos> = path
+        .iter()
+        .zip(path.iter().cycle().skip(1))
+        .flat_map(|(&(r1, c1), &(r2, c2))| [(r1 * 2, c1 * 2), (r1 + r2, c1 + c2)])
+        .collect();
+    let mut tiles = vec![vec![b'.'; side]; side];
+    for tile in tiles.iter_mut().flatten() {
+        if rng.chance(0.7) {
+            *tile = *rng.choose(b"|-LJ7F");
+        }
+    }
+
+    let arm = |(from_row, from_column): Pos, (row, column): Pos| {
+        (
+            row as isize - from_row as isize,
+            column as isize - from_column as isize,
+        )
+    };
+    for (i, &position) in path.iter().enumerate() {
+        let before = arm(position, path[(i + path.len() - 1) % path.len()]);
+        let after = arm(position, path[(i + 1) % path.len()]);
+        tiles[position.0][position.1] = *b"|-LJ7F"
+            .iter()
+            .find(|&&b| {
+                Tile::new(b).is_some_and(|tile| {
+                    tile.arms().contains(&before) && tile.arms().contains(&after)
+                })
+            })
+            .unwrap();
+    }
+
+    let (row, column) = *rng.choose(&path);
+    tiles[row][column] = b'S';
+    let on_loop: HashSet<Pos> = path.into_iter().collect();
+    for direction in [
+        Direction::North,
+        Direction::South,
+        Direction::West,
+        Direction::East,
+    ] {
+        let next = Point::new(row, column).step_within(direction, side, side);
+        if let Some(next) = next.filter(|&next| !on_loop.contains(&next.into())) {
+            tiles[next.row][next.column] = b'.';
+        }
+    }
+
+    tiles
+        .iter()
+        .map(|row| String::from_utf8_lossy(row) + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!(
+        (1, "", 4),
+        (1, "-2", 8),
+        (2, "-3", 4),
+        (2, "-4", 4),
+        (2, "-5", 8),
+        (2, "-6", 10)
+    );
+
+    #[test]
+    fn test_maze_start() {
+        let maze = Maze::new(&crate::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(maze.start, Point::new(1, 1));
+        assert_eq!(maze.start_tile(), Some(Tile::CornerF));
+    }
+
+    #[test]
+    fn test_start_tile_snapshots() {
+        let examples = [crate::template::read_file("examples", DAY)]
+            .into_iter()
+            .chain((2..=6).map(|part| crate::template::read_file_part("examples", DAY, part)));
+        for (i, input) in examples.enumerate() {
+            crate::assert_snapshot!(format!("day10_example_{}", i + 1), parse(&input).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let input = crate::template::read_file_part("examples", DAY, 3);
+        let image = &render(&input)[0];
+        assert_eq!((image.width(), image.height()), (33, 27));
+        // The top left corner of the loop, and the middle of a contained cell
+        assert_eq!(image.get((4, 4)), Some(WHITE));
+        assert_eq!(image.get((4, 5)), Some(WHITE));
+        assert_eq!(image.get((3, 4)), Some([16, 16, 32]));
+        assert_eq!(image.get((19, 7)), Some([40, 160, 60]));
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..10 {
+            let input = generate(40, &mut Rng::new(seed));
+            let maze = parse(&input).unwrap();
+            assert_eq!(maze.cells.width(), 40);
+            assert_eq!(part_one(&input), Some(maze.loop_cells().len() / 2));
+            assert!(part_two(&input).unwrap() > 0);
+        }
+    }
+
+    #[test]
+    fn test_start_tile_from_any_neighbours() {
+        for (input, tile) in [
+            (".|.\n.S.\n.L.\n", Tile::Vertical),
+            ("...\n-S7\n...\n", Tile::Horizontal),
+            (".F.\n.SJ\n...\n", Tile::CornerL),
+            (".|.\nLS.\n...\n", Tile::CornerJ),
+            ("...\nFS.\n.|.\n", Tile::Corner7),
+            ("...\n.S-\n.J.\n", Tile::CornerF),
+        ] {
+            let maze = Maze::new(input).unwrap();
+            assert_eq!(maze.get(maze.start), tile, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
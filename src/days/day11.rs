@@ -0,0 +1,241 @@
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(11);
+
+pub struct Image {
+    data: Grid<bool>,
+    slow_rows: Vec<usize>,
+    slow_cols: Vec<usize>,
+}
+
+impl Image {
+    fn new(s: &str, slow: usize) -> Result<Self, ParseError> {
+        let data = Grid::try_parse(s, |b| match b {
+            b'#' => Some(true),
+            b'.' => Some(false),
+            _ => None,
+        })?;
+        let slow_rows = data
+            .rows()
+            .map(|row| if row.iter().all(|b| !*b) { slow } else { 0 })
+            .collect();
+        let slow_cols = data
+            .columns()
+            .map(|mut col| if col.all(|b| !*b) { slow } else { 0 })
+            .collect();
+        Ok(Self {
+            data,
+            slow_rows,
+            slow_cols,
+        })
+    }
+
+    fn galaxies(&self) -> Vec<(usize, usize)> {
+        self.data
+            .iter()
+            .filter_map(|(pos, b)| if *b { Some(pos) } else { None })
+            .collect()
+    }
+
+    fn sum_paths(&self) -> usize {
+        self.galaxies()
+            .iter()
+            .tuple_combinations()
+            .map(|(&(r1, c1), &(r2, c2))| {
+                let (r1, r2) = if r1 > r2 { (r2, r1) } else { (r1, r2) };
+                let (c1, c2) = if c1 > c2 { (c2, c1) } else { (c1, c2) };
+                self.slow_cols[c1..c2].iter().sum::<usize>()
+                    + self.slow_rows[r1..r2].iter().sum::<usize>()
+                    + (r2 - r1)
+                    + (c2 - c1)
+            })
+            .sum()
+    }
+}
+
+/// The image, with each empty row and column counting as `1 + expansion` of them.
+pub fn parse(input: &str, expansion: usize) -> Result<Image, ParseError> {
+    Image::new(input, expansion)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let image = parse(input, 1).reported()?;
+    Some(image.sum_paths())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let image = parse(input, 1_000_000 - 1).reported()?;
+    Some(image.sum_paths())
+}
+
+/// A random `size` by `size` image with galaxies scattered about as thinly as the real ones,
+/// and some rows and columns left empty to expand.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let empty_rows: Vec<bool> = (0..size).map(|_| rng.chance(0.1)).collect();
+    let empty_columns: Vec<bool> = (0..size).map(|_| rng.chance(0.1)).collect();
+    let mut input = String::new();
+    for &empty_row in &empty_rows {
+        for &empty_column in &empty_columns {
+            let galaxy = !empty_row && !empty_column && rng.chance(0.025);
+            input.push(if galaxy { '#' } else { '.' });
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{self, agree, Arbitrary};
+    use std::collections::VecDeque;
+
+    crate::example_tests!((1, "", 374));
+
+    #[test]
+    fn test_part_two_ten() {
+        let image = Image::new(&crate::template::read_file("examples", DAY), 10 - 1).unwrap();
+        assert_eq!(image.sum_paths(), 1030);
+    }
+
+    #[test]
+    fn test_part_two_hundred() {
+        let image = Image::new(&crate::template::read_file("examples", DAY), 100 - 1).unwrap();
+        assert_eq!(image.sum_paths(), 8410);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, &mut Rng::new(11));
+        let image = parse(&input, 1).unwrap();
+        assert_eq!(image.data.height(), 100);
+        assert!(image.galaxies().len() > 100);
+        assert!(image.slow_rows.iter().any(|&slow| slow > 0));
+        assert!(part_two(&input) > part_one(&input));
+    }
+
+    /// A small image, as rows of whether each pixel is a galaxy.
+    #[derive(Clone, Debug)]
+    struct Picture(Vec<Vec<bool>>);
+
+    impl Picture {
+        fn text(&self) -> String {
+            self.0
+                .iter()
+                .map(|row| row.iter().map(|&g| if g { '#' } else { '.' }).join(""))
+                .join("\n")
+        }
+    }
+
+    impl Arbitrary for Picture {
+        fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+            let height = rng.index(size.min(10) + 1) + 1;
+            let width = rng.index(size.min(10) + 1) + 1;
+            Self(
+                (0..height)
+                    .map(|_| (0..width).map(|_| rng.chance(0.15)).collect())
+                    .collect(),
+            )
+        }
+
+        /// Tries taking out each row and column, then each galaxy.
+        fn shrink(&self) -> Vec<Self> {
+            let mut smaller = Vec::new();
+            let (height, width) = (self.0.len(), self.0[0].len());
+            for r in (0..height).filter(|_| height > 1) {
+                let mut rows = self.0.clone();
+                rows.remove(r);
+                smaller.push(Self(rows));
+            }
+            for c in (0..width).filter(|_| width > 1) {
+                let mut rows = self.0.clone();
+                rows.iter_mut().for_each(|row| {
+                    row.remove(c);
+                });
+                smaller.push(Self(rows));
+            }
+            for (r, row) in self.0.iter().enumerate() {
+                for c in (0..width).filter(|&c| row[c]) {
+                    let mut rows = self.0.clone();
+                    rows[r][c] = false;
+                    smaller.push(Self(rows));
+                }
+            }
+            smaller
+        }
+    }
+
+    /// Really adds `expansion` more of each empty row and column, then walks from each
+    /// galaxy to every later one a step at a time.
+    fn brute_force(picture: &Picture, expansion: usize) -> usize {
+        let copies = |empty: bool| if empty { 1 + expansion } else { 1 };
+        let width = picture.0[0].len();
+        let empty_columns: Vec<bool> = (0..width)
+            .map(|c| picture.0.iter().all(|row| !row[c]))
+            .collect();
+        let mut expanded: Vec<Vec<bool>> = Vec::new();
+        for row in &picture.0 {
+            let wide: Vec<bool> = row
+                .iter()
+                .zip(&empty_columns)
+                .flat_map(|(&g, &empty)| vec![g; copies(empty)])
+                .collect();
+            for _ in 0..copies(!row.contains(&true)) {
+                expanded.push(wide.clone());
+            }
+        }
+
+        let (height, width) = (expanded.len(), expanded[0].len());
+        let galaxies: Vec<(usize, usize)> = (0..height)
+            .cartesian_product(0..width)
+            .filter(|&(r, c)| expanded[r][c])
+            .collect();
+        let mut total = 0;
+        for (i, &from) in galaxies.iter().enumerate() {
+            let mut distance = vec![vec![usize::MAX; width]; height];
+            distance[from.0][from.1] = 0;
+            let mut queue = VecDeque::from([from]);
+            while let Some((r, c)) = queue.pop_front() {
+                let next = [
+                    (r + 1, c),
+                    (r, c + 1),
+                    (r.wrapping_sub(1), c),
+                    (r, c.wrapping_sub(1)),
+                ];
+                for (nr, nc) in next {
+                    if nr < height && nc < width && distance[nr][nc] == usize::MAX {
+                        distance[nr][nc] = distance[r][c] + 1;
+                        queue.push_back((nr, nc));
+                    }
+                }
+            }
+            total += galaxies[i + 1..]
+                .iter()
+                .map(|&(r, c)| distance[r][c])
+                .sum::<usize>();
+        }
+        total
+    }
+
+    #[test]
+    fn test_sum_paths_match_brute_force() {
+        for expansion in [1, 4] {
+            property::check(|picture: &Picture| {
+                agree(
+                    brute_force(picture, expansion),
+                    parse(&picture.text(), expansion).unwrap().sum_paths(),
+                )
+            });
+        }
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input, 1).is_ok());
+    }
+}
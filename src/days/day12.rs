@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(12);
+
+fn possible_completions<'a, 'b>(
+    springs: &'a [u8],
+    summary: &'b [usize],
+    cache: &mut HashMap<(&'a [u8], &'b [usize]), usize>,
+) -> usize {
+    let cache_key = (springs, summary);
+    if let Some(&result) = cache.get(&cache_key) {
+        return result;
+    }
+
+    if summary.is_empty() {
+        // We're out of groups to match, so this will be one match if
+        // none of the remaining springs are clearly failed.
+        let any_failed = springs.contains(&b'#');
+        let result = if any_failed { 0 } else { 1 };
+
+        cache.insert(cache_key, result);
+        return result;
+    }
+
+    let needed_space = summary.iter().fold(summary.len() - 1, |space, &group| {
+        space.saturating_add(group)
+    });
+    if springs.len() < needed_space {
+        // The summary specifies more springs than we have, so no match.
+        cache.insert(cache_key, 0);
+        return 0;
+    }
+
+    if springs[0] == b'.' {
+        // Starting with success is about the same as starting with failure, but
+        // less instructive, we can just skip
+        return possible_completions(&springs[1..], summary, cache);
+    }
+
+    // Check the current group
+    let group = summary[0];
+    // All the springs must be failed or unknown
+    let all_non_operational = springs[..group].iter().all(|&b| b != b'.');
+    let end = (group + 1).min(springs.len());
+
+    let mut solutions = 0;
+
+    // If all the current group are failed or unknown -
+    // and: There is a next spring and it's not failed
+    // or: We're at the end of the spring list
+    if all_non_operational
+        && ((springs.len() > group && springs[group] != b'#') || (springs.len() <= group))
+    {
+        // We can recurse and testing the next group of springs
+        solutions += possible_completions(&springs[end..], &summary[1..], cache);
+    }
+
+    // Starting with unknown, we can recurse and scan again just after
+    if springs[0] == b'?' {
+        solutions += possible_completions(&springs[1..], summary, cache)
+    }
+
+    cache.insert(cache_key, solutions);
+    solutions
+}
+
+/// A row of springs, and the sizes of the groups of damaged ones.
+fn record<'a>(line: Span<'a>) -> Result<(&'a str, Vec<usize>), ParseError> {
+    let (springs, summary) = line.split_once(" ")?;
+    if summary.as_str().is_empty() {
+        return Ok((springs.as_str(), vec![]));
+    }
+    let summary = summary
+        .split(",")
+        .map(|group| match group.parse()? {
+            0 => Err(group.error("groups can't be empty")),
+            size => Ok(size),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((springs.as_str(), summary))
+}
+
+pub fn parse(input: &str) -> Result<Vec<(&str, Vec<usize>)>, ParseError> {
+    Span::new(input).lines().map(record).collect()
+}
+
+fn num_completions((springs, summary): &(&str, Vec<usize>)) -> usize {
+    possible_completions(springs.as_bytes(), summary, &mut HashMap::new())
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(parse(input).reported()?.iter().map(num_completions).sum())
+}
+
+pub fn unfold(s: &str) -> String {
+    let Some((record, summary)) = s.split_once(' ') else {
+        unreachable!("no space in line");
+    };
+
+    (0..5).map(|_| record).join("?") + " " + &(0..5).map(|_| summary).join(",")
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    // Check the folded records first, so that errors point at the real input
+    parse(input).reported()?;
+    let unfolded = input.lines().map(unfold).join("\n");
+    Some(
+        parse(&unfolded)
+            .reported()?
+            .iter()
+            .map(num_completions)
+            .sum(),
+    )
+}
+
+/// `size` random rows of springs, with the groups of damaged springs each row really has,
+/// and about half of the springs hidden behind a `?`.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let length = rng.between(4, 20);
+        let springs: Vec<bool> = loop {
+            let springs: Vec<bool> = (0..length).map(|_| rng.chance(0.4)).collect();
+            if springs.contains(&true) {
+                break springs;
+            }
+        };
+        let groups = springs
+            .iter()
+            .group_by(|&&damaged| damaged)
+            .into_iter()
+            .filter(|(damaged, _)| *damaged)
+            .map(|(_, group)| group.count())
+            .join(",");
+        let shown: String = springs
+            .iter()
+            .map(|&damaged| match (rng.chance(0.5), damaged) {
+                (true, _) => '?',
+                (false, true) => '#',
+                (false, false) => '.',
+            })
+            .collect();
+        writeln!(input, "{shown} {groups}").unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{self, agree, Arbitrary};
+    use test_case::test_case;
+
+    crate::example_tests!((1, "", 21), (2, "", 525152));
+
+    #[test_case("? " => 1 ; "Trailing unknown")]
+    #[test_case(". " => 1; "Trailing good")]
+    #[test_case(".?.. " => 1; "Trailing mix of unknown and good")]
+    #[test_case(".# " => 0; "Trailing failed")]
+    #[test_case("??? 10" => 0 ; "Insufficent springs left")]
+    #[test_case(". 1" => 0)]
+    #[test_case("# 1" => 1)]
+    #[test_case(".F 1" => 1)]
+    fn test_num_completions(s: &str) -> usize {
+        num_completions(&record(Span::new(s)).unwrap())
+    }
+
+    #[test]
+    fn test_unfold() {
+        assert_eq!(unfold(".# 1"), ".#?.#?.#?.#?.# 1,1,1,1,1")
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, &mut Rng::new(12));
+        let records = parse(&input).unwrap();
+        assert_eq!(records.len(), 100);
+        // The arrangement the row was made from is always one of the completions
+        assert!(records.iter().all(|record| num_completions(record) >= 1));
+        assert!(part_two(&input).unwrap() >= part_one(&input).unwrap());
+    }
+
+    /// A short row of springs and some group sizes, not necessarily ones that fit.
+    #[derive(Clone, Debug)]
+    struct Row {
+        springs: String,
+        groups: Vec<usize>,
+    }
+
+    impl Arbitrary for Row {
+        fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+            // Every `?` doubles the oracle's work, so the rows stay short
+            let springs = (0..rng.index(size.min(14) + 1))
+                .map(|_| *rng.choose(&['.', '#', '?']))
+                .collect();
+            let groups = (0..rng.index(4)).map(|_| rng.index(4) + 1).collect();
+            Self { springs, groups }
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            let mut smaller = Vec::new();
+            for i in 0..self.springs.len() {
+                let mut springs = self.springs.clone();
+                springs.remove(i);
+                smaller.push(Self {
+                    springs,
+                    ..self.clone()
+                });
+            }
+            for (i, spring) in self.springs.char_indices().filter(|&(_, c)| c != '.') {
+                let mut springs = self.springs.clone();
+                springs.replace_range(i..=i, if spring == '?' { "#" } else { "." });
+                smaller.push(Self {
+                    springs,
+                    ..self.clone()
+                });
+            }
+            for groups in self.groups.shrink() {
+                if !groups.contains(&0) {
+                    smaller.push(Self {
+                        groups,
+                        ..self.clone()
+                    });
+                }
+            }
+            smaller
+        }
+    }
+
+    /// Tries every way of filling in the unknown springs.
+    fn brute_force(row: &Row) -> usize {
+        let unknown: Vec<usize> = row.springs.match_indices('?').map(|(i, _)| i).collect();
+        (0..1_usize << unknown.len())
+            .filter(|filled| {
+                let mut springs = row.springs.clone().into_bytes();
+                for (bit, &i) in unknown.iter().enumerate() {
+                    springs[i] = if filled >> bit & 1 == 1 { b'#' } else { b'.' };
+                }
+                let groups: Vec<usize> = springs
+                    .split(|&b| b == b'.')
+                    .map(<[u8]>::len)
+                    .filter(|&len| len > 0)
+                    .collect();
+                groups == row.groups
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_completions_match_brute_force() {
+        property::check(|row: &Row| {
+            agree(
+                brute_force(row),
+                num_completions(&(row.springs.as_str(), row.groups.clone())),
+            )
+        });
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
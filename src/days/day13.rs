@@ -0,0 +1,248 @@
+use crate::grid::Grid;
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(13);
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Tile {
+    Ash,
+    Rock,
+}
+
+impl Tile {
+    fn new(b: u8) -> Option<Self> {
+        match b {
+            b'.' => Some(Self::Ash),
+            b'#' => Some(Self::Rock),
+            _ => None,
+        }
+    }
+}
+pub struct Image {
+    data: Grid<Tile>,
+    smudge: bool,
+}
+
+#[derive(PartialEq, Debug)]
+enum Reflection {
+    Horizontal(usize),
+    Vertical(usize),
+}
+
+impl Image {
+    fn new(s: Span, smudge: bool) -> Result<Self, ParseError> {
+        let data = Grid::from_span(s, Tile::new)?;
+        Ok(Self { data, smudge })
+    }
+
+    fn reflection(&self) -> Reflection {
+        if let Some(h) = self.horizontal() {
+            return Reflection::Horizontal(h);
+        }
+        if let Some(v) = self.vertical() {
+            return Reflection::Vertical(v);
+        }
+        unreachable!("image should have a reflection")
+    }
+
+    fn reflection_value(&self) -> usize {
+        match self.reflection() {
+            Reflection::Horizontal(h) => h * 100,
+            Reflection::Vertical(v) => v,
+        }
+    }
+
+    fn is_horizontal_mirror(&self, row: usize) -> bool {
+        let above = row..self.data.height();
+        let below = (0..row).rev();
+        let mut matching = 0;
+        let mut matches = 0;
+        for (first, second) in below.zip(above) {
+            for (a, b) in self.data.row(first).iter().zip(self.data.row(second)) {
+                matches += 1;
+                if a == b {
+                    matching += 1;
+                }
+            }
+        }
+        if self.smudge {
+            matching == matches - 1
+        } else {
+            matching == matches
+        }
+    }
+
+    fn horizontal(&self) -> Option<usize> {
+        (1..self.data.height()).find(|&i| self.is_horizontal_mirror(i))
+    }
+
+    fn is_vertical_mirror(&self, col: usize) -> bool {
+        let left = (0..col).rev();
+        let right = col..self.data.width();
+        let mut matching = 0;
+        let mut matches = 0;
+        for (first, second) in left.zip(right) {
+            for (a, b) in self.data.column(first).zip(self.data.column(second)) {
+                matches += 1;
+                if a == b {
+                    matching += 1;
+                }
+            }
+        }
+
+        if self.smudge {
+            matching == matches - 1
+        } else {
+            matching == matches
+        }
+    }
+
+    fn vertical(&self) -> Option<usize> {
+        (1..self.data.width()).find(|&i| self.is_vertical_mirror(i))
+    }
+}
+
+pub fn parse(i: &str, smudge: bool) -> Result<Vec<Image>, ParseError> {
+    Span::new(i)
+        .blocks()
+        .map(|raw| Image::new(raw, smudge))
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let images = parse(input, false).reported()?;
+    Some(images.iter().map(|i| i.reflection_value()).sum())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let images = parse(input, true).reported()?;
+    Some(images.iter().map(|i| i.reflection_value()).sum())
+}
+
+/// How many places differ across the line of reflection above `row`.
+fn mismatches(pattern: &Grid<bool>, row: usize) -> usize {
+    (0..row)
+        .rev()
+        .zip(row..pattern.height())
+        .map(|(above, below)| {
+            pattern
+                .row(above)
+                .iter()
+                .zip(pattern.row(below))
+                .filter(|(a, b)| a != b)
+                .count()
+        })
+        .sum()
+}
+
+/// `size` random patterns of ash and rocks, each with one line of reflection, and one other
+/// line that reflects once a single smudge is cleaned up.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut patterns = vec![];
+    while patterns.len() < size {
+        let height = rng.between(5, 17) as usize;
+        let width = rng.between(5, 17) as usize;
+        let across = rng.between(1, height as i64 - 1) as usize;
+        let down = rng.between(1, width as i64 - 1) as usize;
+
+        // Mirror each row about `down`, then the rows about `across`
+        let cells = (0..width * height).map(|_| rng.chance(0.5)).collect();
+        let mut pattern = Grid::new(width, height, cells);
+        for row in 0..height {
+            let row = pattern.row_mut(row);
+            for column in down..width.min(down * 2) {
+                row[column] = row[down * 2 - 1 - column];
+            }
+        }
+        for row in across..height.min(across * 2) {
+            for column in 0..width {
+                pattern[(row, column)] = pattern[(across * 2 - 1 - row, column)];
+            }
+        }
+
+        // A smudge in a row that has no reflection across `across` only spoils `down`
+        let unreflected: Vec<usize> = (0..height)
+            .filter(|&row| row + height < across * 2 || row >= across * 2)
+            .collect();
+        if unreflected.is_empty() {
+            continue;
+        }
+        let row = *rng.choose(&unreflected);
+        let column = rng.between((down * 2).saturating_sub(width) as i64, down as i64 - 1);
+        pattern[(row, column as usize)] ^= true;
+
+        // Random rows can line up by chance, so make sure only the lines we chose work
+        let transposed = pattern.transposed();
+        let lines = (1..height)
+            .map(|row| mismatches(&pattern, row))
+            .chain((1..width).map(|column| mismatches(&transposed, column)))
+            .collect_vec();
+        if lines.iter().filter(|&&m| m == 0).count() != 1
+            || lines.iter().filter(|&&m| m == 1).count() != 1
+        {
+            continue;
+        }
+
+        let pattern = if rng.chance(0.5) { transposed } else { pattern };
+        patterns.push(
+            pattern
+                .rows()
+                .map(|row| {
+                    row.iter()
+                        .map(|&rock| if rock { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .join("\n"),
+        );
+    }
+    patterns.join("\n\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 405), (2, "", 400));
+
+    #[test]
+    fn test_reflection_image0() {
+        let images = parse(&crate::template::read_file("examples", DAY), false).unwrap();
+        assert_eq!(images[0].reflection(), Reflection::Vertical(5));
+    }
+
+    #[test]
+    fn test_reflection_image1() {
+        let images = parse(&crate::template::read_file("examples", DAY), false).unwrap();
+        assert_eq!(images[1].reflection(), Reflection::Horizontal(4));
+    }
+
+    #[test]
+    fn test_reflection_image0_smudged() {
+        let images = parse(&crate::template::read_file("examples", DAY), true).unwrap();
+        assert_eq!(images[0].reflection(), Reflection::Horizontal(3));
+    }
+
+    #[test]
+    fn test_reflection_image1_smudged() {
+        let images = parse(&crate::template::read_file("examples", DAY), true).unwrap();
+        assert_eq!(images[1].reflection(), Reflection::Horizontal(1));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(50, &mut Rng::new(13));
+        assert_eq!(parse(&input, false).unwrap().len(), 50);
+        assert!(part_one(&input).is_some());
+        assert!(part_two(&input).is_some());
+        assert_ne!(part_one(&input), part_two(&input));
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input, true).is_ok());
+    }
+}
@@ -0,0 +1,355 @@
+use std::fmt::Display;
+
+use crate::cycles;
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use crate::visualize::{Colour, Frame, Visualize};
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(14);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tile {
+    Ball,
+    Pillar,
+    Empty,
+}
+
+impl Tile {
+    fn new(b: u8) -> Option<Self> {
+        match b {
+            b'O' => Some(Tile::Ball),
+            b'#' => Some(Tile::Pillar),
+            b'.' => Some(Tile::Empty),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match &self {
+                Tile::Ball => 'O',
+                Tile::Pillar => '#',
+                Tile::Empty => '.',
+            }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Rocks {
+    data: Grid<Tile>,
+}
+
+impl Display for Rocks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.data)?;
+        writeln!(f)
+    }
+}
+
+impl Rocks {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            data: Grid::try_parse(s, Tile::new)?,
+        })
+    }
+
+    fn roll_north(&mut self) {
+        for col in 0..self.data.width() {
+            let mut newcol: Vec<Tile> = Vec::new();
+            for (pillar, group) in &self
+                .data
+                .column(col)
+                .copied()
+                .group_by(|&t| t == Tile::Pillar)
+            {
+                if pillar {
+                    newcol.extend(group)
+                } else {
+                    // sorting the group will make all the Ball 'roll' up before Empty
+                    newcol.extend(group.sorted())
+                }
+            }
+            for (r, &v) in newcol.iter().enumerate() {
+                self.data[(r, col)] = v
+            }
+        }
+    }
+
+    fn roll_south(&mut self) {
+        for col in 0..self.data.width() {
+            let mut newcol: Vec<Tile> = Vec::new();
+            for (pillar, group) in &self
+                .data
+                .column(col)
+                .copied()
+                .group_by(|&t| t == Tile::Pillar)
+            {
+                if pillar {
+                    newcol.extend(group)
+                } else {
+                    // sorting the group will make all the Ball 'roll' up before Empty
+                    newcol.extend(group.sorted().rev())
+                }
+            }
+            for (r, &v) in newcol.iter().enumerate() {
+                self.data[(r, col)] = v
+            }
+        }
+    }
+
+    fn roll_west(&mut self) {
+        for row in 0..self.data.height() {
+            let mut newrow: Vec<Tile> = Vec::new();
+            for (pillar, group) in &self.data.row(row).iter().group_by(|t| **t == Tile::Pillar) {
+                if pillar {
+                    newrow.extend(group)
+                } else {
+                    // sorting the group will make all the Ball 'roll' up before Empty
+                    newrow.extend(group.sorted())
+                }
+            }
+            self.data.row_mut(row).copy_from_slice(&newrow);
+        }
+    }
+
+    fn roll_east(&mut self) {
+        for row in 0..self.data.height() {
+            let mut newrow: Vec<Tile> = Vec::new();
+            for (pillar, group) in &self.data.row(row).iter().group_by(|t| **t == Tile::Pillar) {
+                if pillar {
+                    newrow.extend(group)
+                } else {
+                    // sorting the group will make all the Ball 'roll' up before Empty
+                    newrow.extend(group.sorted().rev())
+                }
+            }
+            self.data.row_mut(row).copy_from_slice(&newrow);
+        }
+    }
+
+    /// Rolls every ball towards `(dr, dc)` by moving it into the next free slot of its lane,
+    /// rather than regrouping whole rows and columns like the `roll_*` functions.
+    fn tilt(&mut self, (dr, dc): (isize, isize)) {
+        let vertical = dr != 0;
+        let reversed = dr > 0 || dc > 0;
+        let (lanes, len) = if vertical {
+            (self.data.width(), self.data.height())
+        } else {
+            (self.data.height(), self.data.width())
+        };
+
+        for lane in 0..lanes {
+            let pos = |i: usize| {
+                let i = if reversed { len - 1 - i } else { i };
+                if vertical {
+                    (i, lane)
+                } else {
+                    (lane, i)
+                }
+            };
+
+            let mut free = 0;
+            for i in 0..len {
+                let (r, c) = pos(i);
+                match self.data[(r, c)] {
+                    Tile::Pillar => free = i + 1,
+                    Tile::Ball => {
+                        let (fr, fc) = pos(free);
+                        self.data[(r, c)] = Tile::Empty;
+                        self.data[(fr, fc)] = Tile::Ball;
+                        free += 1;
+                    }
+                    Tile::Empty => {}
+                }
+            }
+        }
+    }
+
+    fn spin_tilt(&mut self) {
+        self.tilt((-1, 0));
+        self.tilt((0, -1));
+        self.tilt((1, 0));
+        self.tilt((0, 1));
+    }
+
+    fn spin(&mut self) {
+        self.roll_north();
+        self.roll_west();
+        self.roll_south();
+        self.roll_east();
+    }
+
+    fn north_weight(&self) -> usize {
+        let depth = self.data.height();
+        let mut result = 0;
+        for (r, row) in self.data.rows().enumerate() {
+            result += row.iter().filter(|t| **t == Tile::Ball).count() * (depth - r);
+        }
+        result
+    }
+
+    fn spin_cycle(&mut self, spin: fn(&mut Self)) -> usize {
+        let next = |data: &Grid<Tile>| {
+            let mut rocks = Rocks { data: data.clone() };
+            spin(&mut rocks);
+            rocks.data
+        };
+        self.data = cycles::nth_state(self.data.clone(), next, 1_000_000_000);
+        self.north_weight()
+    }
+}
+
+impl Visualize for Rocks {
+    fn frame(&self) -> Frame {
+        let mut frame = Frame::from_grid(&self.data, |&tile| match tile {
+            Tile::Ball => 'O',
+            Tile::Pillar => '#',
+            Tile::Empty => '·',
+        });
+        for (pos, &tile) in self.data.iter() {
+            match tile {
+                Tile::Ball => frame.paint(pos, Colour::Yellow),
+                Tile::Pillar | Tile::Empty => frame.paint(pos, Colour::Grey),
+            }
+        }
+        frame.with_caption(format!("load {}", self.north_weight()))
+    }
+}
+
+/// Part one rolls the rocks north, part two goes through the first few spin cycles.
+pub fn visualize(input: &str, part: u8) -> Vec<Frame> {
+    let Some(mut rocks) = parse(input).reported() else {
+        return Vec::new();
+    };
+    type Roll = fn(&mut Rocks);
+    let spin: [(&str, Roll); 4] = [
+        ("north", Rocks::roll_north),
+        ("west", Rocks::roll_west),
+        ("south", Rocks::roll_south),
+        ("east", Rocks::roll_east),
+    ];
+    let rolls = if part == 1 { &spin[..1] } else { &spin[..] };
+    let cycles = if part == 1 { 1 } else { 3 };
+
+    let mut frames = vec![rocks.frame()];
+    for cycle in 1..=cycles {
+        for (name, roll) in rolls {
+            roll(&mut rocks);
+            let frame = rocks.frame();
+            let caption = format!("cycle {cycle}, rolled {name}, {}", frame.caption());
+            frames.push(frame.with_caption(caption));
+        }
+    }
+    frames
+}
+
+pub fn parse(input: &str) -> Result<Rocks, ParseError> {
+    Rocks::new(input)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let mut rocks = parse(input).reported()?;
+    rocks.roll_north();
+    Some(rocks.north_weight())
+}
+
+pub fn part_one_tilt(input: &str) -> Option<usize> {
+    let mut rocks = parse(input).reported()?;
+    rocks.tilt((-1, 0));
+    Some(rocks.north_weight())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let mut rocks = parse(input).reported()?;
+    Some(rocks.spin_cycle(Rocks::spin))
+}
+
+pub fn part_two_tilt(input: &str) -> Option<usize> {
+    let mut rocks = parse(input).reported()?;
+    Some(rocks.spin_cycle(Rocks::spin_tilt))
+}
+
+/// A random `size` by `size` platform, with about as many round and cube-shaped rocks as
+/// the real one.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(match rng.below(100) {
+                0..=19 => 'O',
+                20..=34 => '#',
+                _ => '.',
+            });
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 136), (2, "", 64));
+
+    #[test]
+    fn test_tilt_matches_roll() {
+        let input = crate::template::read_file("examples", DAY);
+        let mut rolled = Rocks::new(&input).unwrap();
+        let mut tilted = rolled.clone();
+        for _ in 0..3 {
+            rolled.spin();
+            tilted.spin_tilt();
+            assert_eq!(rolled, tilted);
+        }
+        assert_eq!(part_one_tilt(&input), Some(136));
+        assert_eq!(part_two_tilt(&input), Some(64));
+    }
+
+    #[test]
+    fn test_roll_snapshots() {
+        let input = crate::template::read_file("examples", DAY);
+        let mut rocks = parse(&input).unwrap();
+        rocks.roll_north();
+        crate::assert_snapshot!("day14_north", rocks);
+
+        let mut rocks = parse(&input).unwrap();
+        for cycle in 1..=3 {
+            rocks.spin();
+            crate::assert_snapshot!(format!("day14_cycle_{cycle}"), rocks);
+        }
+    }
+
+    #[test]
+    fn test_visualize() {
+        let input = crate::template::read_file("examples", DAY);
+        let frames = visualize(&input, 2);
+        assert_eq!(frames.len(), 13);
+        assert_eq!(frames[0].caption(), "load 104");
+        assert_eq!(frames[4].caption(), "cycle 1, rolled east, load 87");
+        assert_eq!(frames[4].to_string().lines().nth(2), Some("····#···O#"));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(50, &mut Rng::new(14));
+        let rocks = parse(&input).unwrap();
+        assert_eq!(rocks.data.width(), 50);
+        assert_eq!(part_one(&input), part_one_tilt(&input));
+        assert_eq!(part_two(&input), part_two_tilt(&input));
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(15);
+
+fn hoho_hash(s: &str) -> usize {
+    let mut acc: usize = 0;
+    for b in s.bytes() {
+        acc += b as usize;
+        acc *= 17;
+        acc %= 256;
+    }
+    acc
+}
+
+/// The steps of the initialization sequence.
+pub fn parse(input: &str) -> Vec<&str> {
+    input.trim().split(',').collect()
+}
+
+fn hash_instructions(steps: &[&str]) -> usize {
+    steps.iter().map(|step| hoho_hash(step)).sum()
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(hash_instructions(&parse(input)))
+}
+
+#[derive(Debug)]
+struct Lens<'a> {
+    label: &'a str,
+    length: usize,
+}
+
+/// What a step of the sequence does with the lens labelled `label`.
+#[derive(Debug)]
+enum Operation<'a> {
+    Insert(Lens<'a>),
+    Remove(&'a str),
+}
+
+/// The steps of the sequence, as operations on lenses.
+fn operations(input: &str) -> Result<Vec<Operation<'_>>, ParseError> {
+    Span::new(input)
+        .trim()
+        .split(",")
+        .map(|step| match step.split_once("=") {
+            Ok((label, length)) => match length.parse()? {
+                length @ 1..=9 => Ok(Operation::Insert(Lens {
+                    label: label.as_str(),
+                    length,
+                })),
+                _ => Err(length.error("focal lengths go from 1 to 9")),
+            },
+            Err(_) => Ok(Operation::Remove(step.strip_suffix("-")?.as_str())),
+        })
+        .collect()
+}
+
+fn focusing_power(operations: Vec<Operation>) -> usize {
+    const SLOTS: usize = 256;
+    let mut boxes: [Vec<Lens>; SLOTS] = [(); SLOTS].map(|()| Vec::new());
+
+    for operation in operations {
+        match operation {
+            Operation::Insert(lens) => {
+                let hash = hoho_hash(lens.label);
+                if let Some(index) = boxes[hash].iter().position(|l| l.label == lens.label) {
+                    boxes[hash][index].length = lens.length;
+                } else {
+                    boxes[hash].push(lens);
+                }
+            }
+            Operation::Remove(label) => {
+                let hash = hoho_hash(label);
+                if let Some(index) = boxes[hash].iter().position(|l| l.label == label) {
+                    boxes[hash].remove(index);
+                }
+            }
+        }
+    }
+
+    let mut power = 0;
+    for (i, b) in boxes.iter().enumerate() {
+        for (j, lens) in b.iter().enumerate() {
+            power += (i + 1) * (j + 1) * lens.length;
+        }
+    }
+    power
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(focusing_power(operations(input).reported()?))
+}
+
+/// `size` random steps of the initialization sequence. The labels are shared between steps,
+/// so lenses get replaced and removed as well as added.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let labels: Vec<String> = (0..(size / 8).max(1))
+        .map(|_| {
+            let length = rng.between(2, 6) as usize;
+            rng.names(1, length).remove(0)
+        })
+        .collect();
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let label = rng.choose(&labels);
+            if rng.chance(0.6) {
+                format!("{label}={}", rng.between(1, 9))
+            } else {
+                format!("{label}-")
+            }
+        })
+        .collect();
+    steps.join(",") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 1320), (2, "", 145));
+
+    #[test]
+    fn test_hoho_hash() {
+        let result = hoho_hash("HASH");
+        assert_eq!(result, 52);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(1000, &mut Rng::new(15));
+        assert_eq!(parse(&input).len(), 1000);
+        assert!(part_one(&input).is_some());
+        assert!(part_two(&input).unwrap() > 0);
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| {
+            !parse(input).is_empty() && operations(input).is_ok()
+        });
+    }
+}
@@ -0,0 +1,290 @@
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::image::{self, Image};
+use crate::parse::ParseError;
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use crate::visualize::{Colour, Frame};
+
+use std::collections::HashSet;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(16);
+
+#[derive(Clone, Copy)]
+pub enum Cell {
+    Empty,
+    Vertical,
+    Horizontal,
+    Forward,
+    Back,
+}
+
+impl Cell {
+    fn new(b: u8) -> Option<Self> {
+        use Cell::*;
+        Some(match b {
+            b'.' => Empty,
+            b'|' => Vertical,
+            b'-' => Horizontal,
+            b'/' => Forward,
+            b'\\' => Back,
+            _ => return None,
+        })
+    }
+
+    fn symbol(self) -> char {
+        use Cell::*;
+        match self {
+            Empty => '·',
+            Vertical => '|',
+            Horizontal => '-',
+            Forward => '/',
+            Back => '\\',
+        }
+    }
+
+    /// The cell as a 3 by 3 pattern of pixels, row by row.
+    fn pixels(self) -> [[bool; 3]; 3] {
+        use Cell::*;
+        const O: bool = false;
+        const X: bool = true;
+        match self {
+            Empty => [[O; 3]; 3],
+            Vertical => [[O, X, O]; 3],
+            Horizontal => [[O, O, O], [X, X, X], [O, O, O]],
+            Forward => [[O, O, X], [O, X, O], [X, O, O]],
+            Back => [[X, O, O], [O, X, O], [O, O, X]],
+        }
+    }
+
+    /// The way a beam heading in `direction` leaves this cell, and the way the other half
+    /// goes if it is split.
+    fn bounce(self, direction: Direction) -> (Direction, Option<Direction>) {
+        use Cell::*;
+        use Direction::*;
+        match (self, direction) {
+            (Empty, _) | (Horizontal, East | West) | (Vertical, North | South) => {
+                // just keep moving
+                (direction, None)
+            }
+            (Back, North | South) | (Forward, West | East) => (direction.left(), None),
+            (Back, West | East) | (Forward, North | South) => (direction.right(), None),
+            (Vertical, East | West) | (Horizontal, North | South) => {
+                (direction.right(), Some(direction.left()))
+            }
+        }
+    }
+}
+
+pub struct Maze {
+    data: Grid<Cell>,
+}
+
+impl Maze {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            data: Grid::try_parse(s, Cell::new)?,
+        })
+    }
+
+    fn energised(&self, start: (Point<usize>, Direction)) -> usize {
+        self.energised_cells(start).len()
+    }
+
+    fn energised_cells(&self, start: (Point<usize>, Direction)) -> HashSet<Point<usize>> {
+        let mut queue = vec![start];
+        let mut visited = HashSet::new();
+
+        'path: while let Some((mut position, mut direction)) = queue.pop() {
+            loop {
+                if !visited.insert((position, direction)) {
+                    continue 'path;
+                };
+                let (onwards, split) = self.data[position].bounce(direction);
+                if let Some(split) = split {
+                    queue.push((position, split));
+                }
+                direction = onwards;
+
+                match position.step_within(direction, self.data.height(), self.data.width()) {
+                    Some(next) => position = next,
+                    None => continue 'path,
+                }
+            }
+        }
+
+        visited.into_iter().map(|(position, _)| position).collect()
+    }
+
+    /// Every place a beam could come in from the edge.
+    fn starts(&self) -> Vec<(Point<usize>, Direction)> {
+        let (height, width) = (self.data.height(), self.data.width());
+        let mut starts = Vec::new();
+        starts.extend((0..height).map(|r| (Point::new(r, 0), Direction::East)));
+        starts.extend((0..height).map(|r| (Point::new(r, width - 1), Direction::West)));
+
+        starts.extend((0..width).map(|c| (Point::new(0, c), Direction::South)));
+        starts.extend((0..width).map(|c| (Point::new(height - 1, c), Direction::North)));
+        starts
+    }
+
+    /// The beams spreading out from `start`, moving every beam one cell per frame.
+    fn beam_frames(&self, start: (Point<usize>, Direction)) -> Vec<Frame> {
+        let mut frame = Frame::from_grid(&self.data, |&cell| cell.symbol());
+        for (pos, _) in self.data.iter() {
+            frame.paint(pos, Colour::Grey);
+        }
+
+        let mut frames = Vec::new();
+        let mut energised = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut beams = vec![start];
+        while !beams.is_empty() {
+            let mut next_beams = Vec::new();
+            for (position, direction) in beams {
+                if !visited.insert((position, direction)) {
+                    continue;
+                }
+                energised.insert(position);
+                let pos = position.into();
+                match self.data[position] {
+                    Cell::Empty if direction.is_vertical() => {
+                        frame.draw(pos, '│', Some(Colour::Yellow));
+                    }
+                    Cell::Empty => frame.draw(pos, '─', Some(Colour::Yellow)),
+                    _ => frame.paint(pos, Colour::Red),
+                }
+
+                let (onwards, split) = self.data[position].bounce(direction);
+                for direction in std::iter::once(onwards).chain(split) {
+                    if let Some(next) =
+                        position.step_within(direction, self.data.height(), self.data.width())
+                    {
+                        next_beams.push((next, direction));
+                    }
+                }
+            }
+            frames.push(
+                frame
+                    .clone()
+                    .with_caption(format!("{} energised", energised.len())),
+            );
+            beams = next_beams;
+        }
+        frames
+    }
+}
+
+pub fn parse(input: &str) -> Result<Maze, ParseError> {
+    Maze::new(input)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let maze = parse(input).reported()?;
+    Some(maze.energised((Point::new(0, 0), Direction::East)))
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let maze = parse(input).reported()?;
+    maze.starts()
+        .into_iter()
+        .map(|start| maze.energised(start))
+        .max()
+}
+
+/// The beams from the top left for part one, and from the best place to start for part two.
+pub fn visualize(input: &str, part: u8) -> Vec<Frame> {
+    let Some(maze) = parse(input).reported() else {
+        return Vec::new();
+    };
+    let start = if part == 1 {
+        (Point::new(0, 0), Direction::East)
+    } else {
+        maze.starts()
+            .into_iter()
+            .max_by_key(|&start| maze.energised(start))
+            .expect("there are places to start")
+    };
+    maze.beam_frames(start)
+}
+
+/// A heat map of how many of the places to start energise each cell, from black for none
+/// to white for all of them, with the mirrors and splitters drawn over it.
+pub fn render(input: &str) -> Vec<Image> {
+    const SCALE: usize = 3;
+    let Some(maze) = parse(input).reported() else {
+        return Vec::new();
+    };
+    let starts = maze.starts();
+    let mut counts: Grid<usize> = maze.data.map(|_| 0);
+    for &start in &starts {
+        for position in maze.energised_cells(start) {
+            counts[(position.row, position.column)] += 1;
+        }
+    }
+
+    let mut image = Image::from_grid(&counts, SCALE, |&count| {
+        image::heat(count as f64 / starts.len() as f64)
+    });
+    for ((row, column), cell) in maze.data.iter() {
+        for (dr, line) in cell.pixels().iter().enumerate() {
+            for (dc, &on) in line.iter().enumerate() {
+                if on {
+                    image.set((row * SCALE + dr, column * SCALE + dc), [90, 140, 255]);
+                }
+            }
+        }
+    }
+    vec![image]
+}
+
+/// A random `size` by `size` contraption, mostly empty space with some mirrors and splitters.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(if rng.chance(0.12) {
+                char::from(*rng.choose(b"|-/\\"))
+            } else {
+                '.'
+            });
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 46), (2, "", 51));
+
+    #[test]
+    fn test_visualize() {
+        let frames = visualize(&crate::template::read_file("examples", DAY), 2);
+        assert_eq!(frames.last().unwrap().caption(), "51 energised");
+    }
+
+    #[test]
+    fn test_render() {
+        let image = &render(&crate::template::read_file("examples", DAY))[0];
+        assert_eq!((image.width(), image.height()), (30, 30));
+        // The top left splitter, over a cell that every beam but one passes through
+        assert_eq!(image.get((1, 4)), Some([90, 140, 255]));
+        assert_ne!(image.get((0, 3)), Some(image::BLACK));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(40, &mut Rng::new(16));
+        assert_eq!(parse(&input).unwrap().starts().len(), 160);
+        assert!(part_two(&input) >= part_one(&input));
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
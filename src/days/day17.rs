@@ -0,0 +1,227 @@
+use crate::geometry::{Direction, Point};
+use crate::grid::{Grid, Pos};
+use crate::parse::ParseError;
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use crate::visualize::{Colour, Frame};
+use pathfinding::prelude::dijkstra;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(17);
+
+#[derive(Default, Clone, PartialEq, Eq, Hash)]
+struct Cart {
+    position: Point<usize>,
+    direction: Option<Direction>,
+    run: usize,
+}
+
+impl Cart {
+    fn new(row: usize, column: usize) -> Self {
+        Self {
+            position: Point::new(row, column),
+            ..Default::default()
+        }
+    }
+
+    fn go(
+        &self,
+        direction: Direction,
+        can_turn: fn(usize) -> bool,
+        must_turn: fn(usize) -> bool,
+        maze: &Maze,
+    ) -> Option<Self> {
+        let mut next = self.clone();
+        next.direction = Some(direction);
+
+        // Track and reject based on where we've been
+        if let Some(heading) = &self.direction {
+            if *heading == direction.reverse() {
+                // we can only go straight on, left, or right
+                return None;
+            }
+
+            if *heading == direction {
+                // continue forward
+                next.run += 1;
+
+                // unless we've being going forward too long
+                if must_turn(next.run) {
+                    return None;
+                }
+            } else {
+                // a turn, are we allowed it?
+                if can_turn(next.run) {
+                    next.run = 0;
+                } else {
+                    return None;
+                }
+            }
+        }
+
+        // compute position or None if it'll take us out of bounds
+        next.position =
+            self.position
+                .step_within(direction, maze.data.height(), maze.data.width())?;
+
+        Some(next)
+    }
+
+    fn successors(
+        &self,
+        can_turn: fn(usize) -> bool,
+        must_turn: fn(usize) -> bool,
+        maze: &Maze,
+    ) -> Vec<(Self, u32)> {
+        let neighbours = Direction::ALL
+            .into_iter()
+            .filter_map(|d| self.go(d, can_turn, must_turn, maze));
+        neighbours
+            .map(|cart| {
+                let cost = maze.cost(cart.position);
+                (cart, cost)
+            })
+            .collect()
+    }
+}
+
+/// When a crucible can turn, and when it must, by how far it has gone in a straight line.
+type Steering = (fn(usize) -> bool, fn(usize) -> bool);
+
+pub struct Maze {
+    data: Grid<u8>,
+}
+
+impl Maze {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            data: Grid::try_parse(s, |b| b.is_ascii_digit().then(|| b - b'0'))?,
+        })
+    }
+
+    fn cost(&self, position: Point<usize>) -> u32 {
+        self.data[position] as u32
+    }
+
+    fn best_path(
+        &self,
+        can_turn: fn(usize) -> bool,
+        must_turn: fn(usize) -> bool,
+    ) -> (Vec<Cart>, u32) {
+        let goal = Point::new(self.data.height() - 1, self.data.width() - 1);
+        let Some(found) = dijkstra(
+            &Cart::new(0, 0),
+            |cart| cart.successors(can_turn, must_turn, self),
+            |cart| cart.position == goal,
+        ) else {
+            unreachable!("should have a path");
+        };
+
+        found
+    }
+
+    fn min_heat_loss(&self, can_turn: fn(usize) -> bool, must_turn: fn(usize) -> bool) -> u32 {
+        self.best_path(can_turn, must_turn).1
+    }
+
+    fn crucible_heat_loss(&self) -> u32 {
+        self.min_heat_loss(Self::CRUCIBLE.0, Self::CRUCIBLE.1)
+    }
+
+    fn ultracrucible_heat_loss(&self) -> u32 {
+        self.min_heat_loss(Self::ULTRACRUCIBLE.0, Self::ULTRACRUCIBLE.1)
+    }
+
+    const CRUCIBLE: Steering = (|_| true, |step| step >= 3);
+    const ULTRACRUCIBLE: Steering = (|step| step >= 3, |step| step >= 10);
+}
+
+pub fn parse(input: &str) -> Result<Maze, ParseError> {
+    Maze::new(input)
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let maze = parse(input).reported()?;
+    Some(maze.crucible_heat_loss())
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let maze = parse(input).reported()?;
+    Some(maze.ultracrucible_heat_loss())
+}
+
+/// The crucible working its way across the city, over the heat loss of each block.
+pub fn visualize(input: &str, part: u8) -> Vec<Frame> {
+    let Some(maze) = parse(input).reported() else {
+        return Vec::new();
+    };
+    let (can_turn, must_turn) = if part == 1 {
+        Maze::CRUCIBLE
+    } else {
+        Maze::ULTRACRUCIBLE
+    };
+    let (carts, _) = maze.best_path(can_turn, must_turn);
+    let path: Vec<Pos> = carts.iter().map(|cart| cart.position.into()).collect();
+
+    let mut frame = Frame::from_grid(&maze.data, |&heat| char::from(b'0' + heat));
+    for (pos, &heat) in maze.data.iter() {
+        let colour = if heat < 5 {
+            Colour::Grey
+        } else {
+            Colour::White
+        };
+        frame.paint(pos, colour);
+    }
+
+    let stride = path.len().div_ceil(60).max(1);
+    let mut frames = Vec::new();
+    for end in (1..path.len()).step_by(stride).chain([path.len()]) {
+        frame.draw_path(&path[..end], Colour::Red);
+        let lost: u32 = path[1..end]
+            .iter()
+            .map(|&pos| u32::from(maze.data[pos]))
+            .sum();
+        frames.push(frame.clone().with_caption(format!("heat lost {lost}")));
+    }
+    frames
+}
+
+/// A random map of `size` by `size` city blocks, each losing one to nine heat. Anything
+/// smaller than five blocks across is made bigger, as the ultra crucible needs room to stop.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(5);
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(char::from(b'1' + rng.below(9) as u8));
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 102), (2, "", 94));
+
+    #[test]
+    fn test_visualize() {
+        let frames = visualize(&crate::template::read_file("examples", DAY), 1);
+        assert_eq!(frames.last().unwrap().caption(), "heat lost 102");
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(30, &mut Rng::new(17));
+        assert_eq!(parse(&input).unwrap().data.width(), 30);
+        assert!(part_two(&input) >= part_one(&input));
+        assert!(part_two(&generate(1, &mut Rng::new(17))).is_some());
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
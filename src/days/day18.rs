@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::geometry::{Direction, Point};
+use crate::grid::Pos;
+use crate::image::{Image, Rgb};
+use crate::parse::{ParseError, Span};
+use crate::polygon::Polygon;
+use crate::random::{random_loop, Rng};
+use crate::template::runner::Reported;
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(18);
+
+#[derive(Debug, PartialEq)]
+struct Instruction {
+    direction: Direction,
+    distance: usize,
+}
+
+/// The direction, distance and hex code of a line of the dig plan, like `R 6 (#70c710)`.
+fn fields(line: Span) -> Result<[Span; 3], ParseError> {
+    let (direction, rest) = line.split_once(" ")?;
+    let (distance, code) = rest.split_once(" ")?;
+    let hex = code.strip_prefix("(#")?.strip_suffix(")")?;
+    if hex.as_str().len() != 6 || !hex.as_str().bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(hex.error(format!("expected 6 hex digits, found {:?}", hex.as_str())));
+    }
+    Ok([direction, distance, hex])
+}
+
+impl Instruction {
+    fn new(line: Span) -> Result<Self, ParseError> {
+        let [direction, distance, _] = fields(line)?;
+        Ok(Self {
+            direction: direction.parse()?,
+            distance: distance.parse()?,
+        })
+    }
+
+    fn hex(line: Span) -> Result<Self, ParseError> {
+        let [_, _, hex] = fields(line)?;
+        let (distance, direction) = hex.as_str().split_at(5);
+        let direction = match direction {
+            "0" | "1" | "2" | "3" => direction.as_bytes()[0],
+            _ => return Err(hex.skip(5).error("expected a direction from 0 to 3")),
+        };
+        Ok(Self {
+            direction: Direction::try_from(direction).map_err(|e| hex.error(e.to_string()))?,
+            distance: usize::from_str_radix(distance, 16).map_err(|e| hex.error(e.to_string()))?,
+        })
+    }
+}
+
+/// The colour of the trench for a line of the dig plan.
+fn colour(line: Span) -> Result<Rgb, ParseError> {
+    let [_, _, hex] = fields(line)?;
+    let channel = |i: usize| {
+        u8::from_str_radix(&hex.as_str()[i..i + 2], 16)
+            .map_err(|e| hex.skip(i).error(e.to_string()))
+    };
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+pub struct Digger {
+    instructions: Vec<Instruction>,
+    /// The colour of the trench dug by each instruction.
+    colours: Vec<Rgb>,
+}
+
+impl Digger {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        Self::with(s, Instruction::new)
+    }
+
+    fn new_hex(s: &str) -> Result<Self, ParseError> {
+        Self::with(s, Instruction::hex)
+    }
+
+    fn with(
+        s: &str,
+        instruction: fn(Span) -> Result<Instruction, ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut digger = Self {
+            instructions: Vec::new(),
+            colours: Vec::new(),
+        };
+        for line in Span::new(s).lines() {
+            digger.instructions.push(instruction(line)?);
+            digger.colours.push(colour(line)?);
+        }
+        Ok(digger)
+    }
+
+    fn cubic_meters(&self) -> usize {
+        // The trench is dug through the middle of each cube, so counting the lattice
+        // points of the polygon counts the cubes dug out
+        let lagoon = Polygon::from_steps(
+            self.instructions
+                .iter()
+                .map(|step| (step.direction, step.distance as i64)),
+        );
+        lagoon.lattice_points() as usize
+    }
+}
+
+pub fn parse(input: &str) -> Result<Digger, ParseError> {
+    Digger::new(input)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let digger = parse(input).reported()?;
+    Some(digger.cubic_meters())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let digger = Digger::new_hex(input).reported()?;
+    Some(digger.cubic_meters())
+}
+
+/// The lagoon from part one, with each stretch of trench in the colour from the dig plan
+/// and the inside dug out. The trench has too many colours to make a good GIF.
+pub fn render(input: &str) -> Vec<Image> {
+    const SCALE: usize = 2;
+    let Some(digger) = parse(input).reported() else {
+        return Vec::new();
+    };
+    let mut trench: HashMap<Point<i64>, Rgb> = HashMap::new();
+    let mut position: Point<i64> = Point::new(0, 0);
+    for (step, &colour) in digger.instructions.iter().zip(&digger.colours) {
+        for _ in 0..step.distance {
+            position = position.step(step.direction);
+            trench.insert(position, colour);
+        }
+    }
+
+    let rows = trench.keys().map(|p| p.row).minmax().into_option().unwrap();
+    let columns = trench
+        .keys()
+        .map(|p| p.column)
+        .minmax()
+        .into_option()
+        .unwrap();
+    let (height, width) = (rows.1 - rows.0 + 1, columns.1 - columns.0 + 1);
+    let lagoon = Polygon::from_steps(
+        digger
+            .instructions
+            .iter()
+            .map(|step| (step.direction, step.distance as i64)),
+    );
+
+    let mut image = Image::new(
+        width as usize * SCALE,
+        height as usize * SCALE,
+        [30, 20, 10],
+    );
+    for row in rows.0..=rows.1 {
+        for column in columns.0..=columns.1 {
+            let point = Point::new(row, column);
+            let colour = match trench.get(&point) {
+                Some(&colour) => colour,
+                None if lagoon.contains(point) => [70, 50, 35],
+                None => continue,
+            };
+            let corner = ((row - rows.0) as usize, (column - columns.0) as usize);
+            image.fill((corner.0 * SCALE, corner.1 * SCALE), (SCALE, SCALE), colour);
+        }
+    }
+    vec![image]
+}
+
+/// A random dig plan of about `size` instructions, for a trench that never crosses itself.
+/// The hex codes plan a far bigger lagoon of the same shape, like in part two.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let side = ((size as f64).sqrt() as usize).max(1);
+    let mut path = random_loop(rng, side, side, 0.6);
+
+    // Start on a corner, so the first instruction isn't the end of the last one
+    let step =
+        |(r1, c1): Pos, (r2, c2): Pos| (r2 as isize - r1 as isize, c2 as isize - c1 as isize);
+    let corner = (0..path.len())
+        .find(|&i| {
+            let before = path[(i + path.len() - 1) % path.len()];
+            step(before, path[i]) != step(path[i], path[(i + 1) % path.len()])
+        })
+        .unwrap();
+    path.rotate_left(corner);
+    path.push(path[0]);
+
+    // Stretch the loop's grid by random gaps between neighbouring rows and columns, which
+    // keeps its shape. Gaps of at least two keep separate stretches of trench from touching,
+    // and part two's gaps still have to fit in five hex digits.
+    let cells = side * 2;
+    let mut stretch = |most: i64| -> Vec<i64> {
+        let mut at = 0;
+        (0..cells)
+            .map(|_| {
+                at += rng.between(2, most);
+                at
+            })
+            .collect()
+    };
+    let (rows, columns) = (stretch(6), stretch(6));
+    let most = 0xfffff / cells as i64;
+    let (hex_rows, hex_columns) = (stretch(most), stretch(most));
+
+    let mut input = String::new();
+    for (direction, run) in &path.iter().tuple_windows().group_by(|&(&a, &b)| step(a, b)) {
+        let run: Vec<_> = run.collect();
+        let (from, to) = (run[0].0, run[run.len() - 1].1);
+        let (letter, digit) = match direction {
+            (0, 1) => ('R', 0),
+            (1, 0) => ('D', 1),
+            (0, -1) => ('L', 2),
+            _ => ('U', 3),
+        };
+        let (axis, hex_axis, a, b) = if direction.1 == 0 {
+            (&rows, &hex_rows, from.0, to.0)
+        } else {
+            (&columns, &hex_columns, from.1, to.1)
+        };
+        let (distance, hex) = (axis[a].abs_diff(axis[b]), hex_axis[a].abs_diff(hex_axis[b]));
+        writeln!(input, "{letter} {distance} (#{hex:05x}{digit})").unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{self, agree, Arbitrary};
+    use assert2::check;
+    use std::collections::{HashSet, VecDeque};
+
+    crate::example_tests!((1, "", 62), (2, "", 952_408_144_115));
+
+    #[test]
+    fn test_hex_decoding() {
+        use Direction::*;
+        let digger = Digger::new_hex(&crate::template::read_file("examples", DAY)).unwrap();
+        check!(
+            digger.instructions[..2]
+                == vec![
+                    Instruction {
+                        direction: East,
+                        distance: 461937
+                    },
+                    Instruction {
+                        direction: South,
+                        distance: 56407
+                    }
+                ]
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let image = &render(&crate::template::read_file("examples", DAY))[0];
+        assert_eq!((image.width(), image.height()), (14, 20));
+        // The first stretch of trench, the inside, and the outside
+        check!(image.get((0, 2)) == Some([0x70, 0xc7, 0x10]));
+        check!(image.get((2, 2)) == Some([70, 50, 35]));
+        check!(image.get((16, 0)) == Some([30, 20, 10]));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(200, &mut Rng::new(18));
+        let digger = parse(&input).unwrap();
+        let hex = Digger::new_hex(&input).unwrap();
+        assert_eq!(digger.instructions.len(), hex.instructions.len());
+        assert!(digger.instructions.len() > 50);
+        // Turning at every instruction, and back where it started
+        for plan in [&digger, &hex] {
+            let steps = &plan.instructions;
+            assert!(steps
+                .iter()
+                .zip(steps.iter().cycle().skip(1))
+                .all(|(a, b)| a.direction != b.direction && a.direction != b.direction.reverse()));
+            let end = steps.iter().fold(Point::<i64>::new(0, 0), |at, step| {
+                at.step_n(step.direction, step.distance as i64)
+            });
+            assert_eq!(end, Point::new(0, 0));
+        }
+        assert!(part_two(&input) > part_one(&input));
+    }
+
+    /// A dig plan from [`generate`], shrunk by asking for a smaller one.
+    #[derive(Clone, Debug)]
+    struct Plan {
+        size: usize,
+        seed: u64,
+    }
+
+    impl Plan {
+        fn text(&self) -> String {
+            generate(self.size, &mut Rng::new(self.seed))
+        }
+    }
+
+    impl Arbitrary for Plan {
+        fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+            Self {
+                size: rng.index(size + 1) + 1,
+                seed: rng.next_u64(),
+            }
+        }
+
+        fn shrink(&self) -> Vec<Self> {
+            self.size
+                .shrink()
+                .into_iter()
+                .filter(|&size| size > 0)
+                .map(|size| Self { size, ..*self })
+                .collect()
+        }
+    }
+
+    /// Digs the trench a cube at a time, then fills in from outside it to find what's left.
+    fn flood_fill(digger: &Digger) -> usize {
+        let mut trench = HashSet::from([(0, 0)]);
+        let mut at: (i64, i64) = (0, 0);
+        for step in &digger.instructions {
+            let (dr, dc) = match step.direction {
+                Direction::North => (-1, 0),
+                Direction::South => (1, 0),
+                Direction::West => (0, -1),
+                Direction::East => (0, 1),
+            };
+            for _ in 0..step.distance {
+                at = (at.0 + dr, at.1 + dc);
+                trench.insert(at);
+            }
+        }
+
+        // One cube of room all round, so the outside is all joined up
+        let rows = trench.iter().map(|p| p.0).minmax().into_option().unwrap();
+        let columns = trench.iter().map(|p| p.1).minmax().into_option().unwrap();
+        let (rows, columns) = (
+            (rows.0 - 1)..=(rows.1 + 1),
+            (columns.0 - 1)..=(columns.1 + 1),
+        );
+        let mut outside = HashSet::from([(*rows.start(), *columns.start())]);
+        let mut queue: VecDeque<(i64, i64)> = outside.iter().copied().collect();
+        while let Some((r, c)) = queue.pop_front() {
+            for next in [(r + 1, c), (r - 1, c), (r, c + 1), (r, c - 1)] {
+                if rows.contains(&next.0)
+                    && columns.contains(&next.1)
+                    && !trench.contains(&next)
+                    && outside.insert(next)
+                {
+                    queue.push_back(next);
+                }
+            }
+        }
+        rows.count() * columns.count() - outside.len()
+    }
+
+    #[test]
+    fn test_cubic_meters_match_flood_fill() {
+        property::check(|plan: &Plan| {
+            let digger = parse(&plan.text()).unwrap();
+            agree(flood_fill(&digger), digger.cubic_meters())
+        });
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| {
+            let hex = Digger::new_hex(input).is_ok();
+            parse(input).is_ok() && hex
+        });
+    }
+}
@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::ranges::RangeSet;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(19);
+
+type Value = u64;
+
+enum Op {
+    Gt,
+    Lt,
+}
+
+impl Op {
+    fn new(s: Span) -> Result<Self, ParseError> {
+        use Op::*;
+        match s.as_str().as_bytes().first() {
+            Some(b'>') => Ok(Gt),
+            Some(b'<') => Ok(Lt),
+            _ => Err(s.error("expected > or <")),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Field {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl Field {
+    fn new(s: Span) -> Result<Self, ParseError> {
+        use Field::*;
+        match s.as_str().as_bytes().first() {
+            Some(b'x') => Ok(X),
+            Some(b'm') => Ok(M),
+            Some(b'a') => Ok(A),
+            Some(b's') => Ok(S),
+            _ => Err(s.error("expected one of x, m, a or s")),
+        }
+    }
+}
+
+struct Rule<'a> {
+    field: Field,
+    op: Op,
+    value: Value,
+    target: &'a str,
+}
+
+impl<'a> Rule<'a> {
+    fn new(s: Span<'a>) -> Result<Self, ParseError> {
+        let (expr, target) = s.split_once(":")?;
+        let field = Field::new(expr)?;
+        let op = Op::new(expr.skip(1))?;
+        let value = expr.skip(2).parse()?;
+        Ok(Rule {
+            field,
+            op,
+            value,
+            target: target.as_str(),
+        })
+    }
+
+    fn evaluate(&self, p: &Product) -> Option<&str> {
+        if match self.op {
+            Op::Gt => p.get(self.field) > self.value,
+            Op::Lt => p.get(self.field) < self.value,
+        } {
+            return Some(self.target);
+        }
+        None
+    }
+}
+
+struct Workflow<'a> {
+    rules: Vec<Rule<'a>>,
+    default: &'a str,
+}
+
+impl Workflow<'_> {
+    fn evaluate(&self, p: &Product) -> &str {
+        for rule in &self.rules {
+            if let Some(state) = rule.evaluate(p) {
+                return state;
+            }
+        }
+        self.default
+    }
+}
+
+#[derive(Default)]
+struct Product {
+    x: Value,
+    m: Value,
+    a: Value,
+    s: Value,
+}
+
+impl Product {
+    fn new(s: Span) -> Result<Self, ParseError> {
+        let mut product: Self = Default::default();
+        for field in s.strip_prefix("{")?.strip_suffix("}")?.split(",") {
+            let (key, value) = field.split_once("=")?;
+            let value = value.parse()?;
+            use Field::*;
+            match Field::new(key)? {
+                X => product.x = value,
+                M => product.m = value,
+                A => product.a = value,
+                S => product.s = value,
+            }
+        }
+        Ok(product)
+    }
+
+    fn get(&self, f: Field) -> Value {
+        use Field::*;
+        match f {
+            X => self.x,
+            M => self.m,
+            A => self.a,
+            S => self.s,
+        }
+    }
+
+    fn value(&self) -> Value {
+        self.x + self.m + self.a + self.s
+    }
+}
+
+pub struct System<'a> {
+    workflows: HashMap<&'a str, Workflow<'a>>,
+    products: Vec<Product>,
+}
+
+impl<'a> System<'a> {
+    fn new(s: &'a str) -> Result<Self, ParseError> {
+        let input = Span::new(s);
+        let [workflows, products] = input.array(input.blocks())?;
+        let workflows = workflows
+            .lines()
+            .map(|l| {
+                let (id, rules) = l.split_once("{")?;
+                let mut rules = rules.strip_suffix("}")?.split(",").collect_vec();
+                let finally = rules.pop().unwrap();
+                let rules = rules.into_iter().map(Rule::new).try_collect()?;
+                Ok((
+                    id.as_str(),
+                    Workflow {
+                        rules,
+                        default: finally.as_str(),
+                    },
+                ))
+            })
+            .try_collect()?;
+
+        let products = products.lines().map(Product::new).try_collect()?;
+
+        Ok(Self {
+            workflows,
+            products,
+        })
+    }
+
+    fn accept(&self, workflow: &str, p: &Product) -> bool {
+        let mut workflow = workflow;
+        loop {
+            let flow = self.workflows.get(workflow).unwrap();
+            let next = flow.evaluate(p);
+
+            match next {
+                "A" => return true,
+                "R" => return false,
+                _ => workflow = next,
+            }
+        }
+    }
+
+    fn accepted_sum(&self) -> Value {
+        self.products
+            .iter()
+            .filter(|p| self.accept("in", p))
+            .map(|p| p.value())
+            .sum()
+    }
+}
+
+/// The values each field can still take on the way through the workflows.
+#[derive(Clone)]
+struct ProductRanges([RangeSet<Value>; 4]);
+
+impl Default for ProductRanges {
+    fn default() -> Self {
+        Self(std::array::from_fn(|_| RangeSet::from(1..4001)))
+    }
+}
+
+impl ProductRanges {
+    /// Splits into the products matching `rule`, and those that fall through.
+    fn split(&self, rule: &Rule) -> (Self, Self) {
+        let field = &self.0[rule.field as usize];
+        let (branch, rest) = match rule.op {
+            Op::Gt => {
+                let (rest, branch) = field.split_at(rule.value + 1);
+                (branch, rest)
+            }
+            Op::Lt => field.split_at(rule.value),
+        };
+
+        let (mut branch_ranges, mut rest_ranges) = (self.clone(), self.clone());
+        branch_ranges.0[rule.field as usize] = branch;
+        rest_ranges.0[rule.field as usize] = rest;
+        (branch_ranges, rest_ranges)
+    }
+
+    fn possible_combinations(&self) -> Value {
+        self.0.iter().map(RangeSet::len).product()
+    }
+}
+
+pub fn parse(input: &str) -> Result<System<'_>, ParseError> {
+    System::new(input)
+}
+
+pub fn part_one(input: &str) -> Option<Value> {
+    let system = parse(input).reported()?;
+    Some(system.accepted_sum())
+}
+
+impl System<'_> {
+    fn accept_ranges(&self, workflow: &str, mut range: ProductRanges) -> Value {
+        let mut result = 0;
+        let workflow = self.workflows.get(workflow).unwrap();
+
+        for rule in &workflow.rules {
+            let (branch_range, rest) = range.split(rule);
+            range = rest;
+
+            result += match rule.target {
+                "A" => branch_range.possible_combinations(),
+                "R" => 0,
+                _ => self.accept_ranges(rule.target, branch_range),
+            }
+        }
+
+        result += match workflow.default {
+            "A" => range.possible_combinations(),
+            "R" => 0,
+            _ => self.accept_ranges(workflow.default, range),
+        };
+
+        result
+    }
+
+    fn possible_combinations(&self) -> Value {
+        self.accept_ranges("in", ProductRanges::default())
+    }
+}
+
+pub fn part_two(input: &str) -> Option<Value> {
+    let system = parse(input).reported()?;
+    Some(system.possible_combinations())
+}
+
+/// A random system of `size` workflows branching out from `in`, each reached from exactly one
+/// rule like the real ones, followed by `size` random parts to sort.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const FIELDS: [char; 4] = ['x', 'm', 'a', 's'];
+    let size = size.max(1);
+    let length = if size < 8000 { 3 } else { 4 };
+    let mut fresh = rng.names(size, length).into_iter();
+    let mut queue = VecDeque::from(["in".to_string()]);
+    let mut budget = size - 1;
+    let mut workflows = vec![];
+    while let Some(name) = queue.pop_front() {
+        let mut targets: Vec<String> = (0..rng.between(2, 4))
+            .map(|_| {
+                // Always branch when nothing else is waiting, so the tree doesn't stop short
+                if budget > 0 && (queue.is_empty() || rng.chance(0.7)) {
+                    budget -= 1;
+                    let child = fresh.next().unwrap();
+                    queue.push_back(child.clone());
+                    child
+                } else {
+                    (if rng.chance(0.5) { "A" } else { "R" }).to_string()
+                }
+            })
+            .collect();
+        let default = targets.pop().unwrap();
+        let rules = targets
+            .iter()
+            .map(|target| {
+                let field = rng.choose(&FIELDS);
+                let op = if rng.chance(0.5) { '<' } else { '>' };
+                format!("{field}{op}{}:{target}", rng.between(1, 4000))
+            })
+            .join(",");
+        workflows.push(format!("{name}{{{rules},{default}}}"));
+    }
+    rng.shuffle(&mut workflows);
+
+    let mut input = workflows.join("\n") + "\n\n";
+    for _ in 0..size {
+        let [x, m, a, s] = [(); 4].map(|()| rng.between(1, 4000));
+        writeln!(input, "{{x={x},m={m},a={a},s={s}}}").unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 19114), (2, "", 167_409_079_868_000));
+
+    #[test]
+    fn test_generate() {
+        let input = generate(200, &mut Rng::new(19));
+        let system = parse(&input).unwrap();
+        assert_eq!(system.workflows.len(), 200);
+        assert_eq!(system.products.len(), 200);
+        assert!(part_two(&input).unwrap() <= 4000_u64.pow(4));
+        assert!(part_one(&input).is_some());
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::cycles::Periodic;
+use crate::numeric::is_prime;
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(20);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Module {
+    Broadcast,
+    FlipFlop,
+    Conjunction,
+}
+
+type Node<'a> = &'a str;
+
+#[derive(Debug, Default)]
+pub struct Network<'a> {
+    connections: HashMap<Node<'a>, Vec<Node<'a>>>,
+    modules: HashMap<Node<'a>, Module>,
+    flipflops: HashMap<Node<'a>, bool>,
+    conjunctions: HashMap<Node<'a>, HashMap<Node<'a>, Pulse>>,
+}
+
+impl<'b> Network<'b> {
+    fn new(s: &'b str) -> Result<Self, ParseError> {
+        let lines: Vec<(Node, Module, Vec<Node>)> = Span::new(s)
+            .lines()
+            .map(|l| {
+                let (source, targets) = l.split_once(" -> ")?;
+                let targets = targets.split(", ").map(|t| t.as_str()).collect();
+                let source = source.as_str();
+                Ok(match source.as_bytes().first() {
+                    Some(b'%') => (&source[1..], Module::FlipFlop, targets),
+                    Some(b'&') => (&source[1..], Module::Conjunction, targets),
+                    Some(_) => (source, Module::Broadcast, targets),
+                    None => return Err(l.error("expected a module name")),
+                })
+            })
+            .collect::<Result<_, ParseError>>()?;
+
+        let connections: HashMap<_, Vec<_>> = lines
+            .iter()
+            .map(|(source, _, targets)| (*source, targets.clone()))
+            .collect();
+
+        let modules: HashMap<_, _> = lines
+            .iter()
+            .map(|&(source, module, _)| (source, module))
+            .collect();
+
+        let conjunctions: HashMap<_, _> = modules
+            .iter()
+            .filter_map(|(&k, &v)| {
+                if v == Module::Conjunction {
+                    Some((
+                        k,
+                        connections
+                            .iter()
+                            .filter_map(|(&from, to)| {
+                                if to.contains(&k) {
+                                    Some((from, Pulse::Low))
+                                } else {
+                                    None
+                                }
+                            })
+                            .collect(),
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            connections,
+            modules,
+            conjunctions,
+            ..Default::default()
+        })
+    }
+
+    fn send<'a, FN>(&mut self, target: Node<'a>, pulse: Pulse, mut peek: FN)
+    where
+        FN: FnMut(Node<'a>, Node<'a>, Pulse),
+        'a: 'b,
+        'b: 'a,
+    {
+        let mut queue: VecDeque<(Node, Node, Pulse)> = VecDeque::new();
+        queue.push_back((target, "", pulse));
+
+        while let Some((current, source, pulse)) = queue.pop_front() {
+            peek(current, source, pulse);
+            let module = self.modules.get_mut(current);
+            let mut send = pulse;
+
+            match (&module, pulse) {
+                (Some(Module::FlipFlop), Pulse::Low) => {
+                    let state = self.flipflops.entry(current).or_default();
+                    *state = !*state;
+                    send = if *state { Pulse::High } else { Pulse::Low };
+                }
+                (Some(Module::FlipFlop), _) => continue,
+                (Some(Module::Conjunction), _) => {
+                    let memory = self.conjunctions.get_mut(current).unwrap();
+                    memory.insert(source, pulse);
+                    send = if memory.values().all(|&v| v == Pulse::High) {
+                        Pulse::Low
+                    } else {
+                        Pulse::High
+                    };
+                }
+                (Some(Module::Broadcast), _) => (),
+                (None, _) => {
+                    continue;
+                }
+            }
+
+            for neighbour in self.connections.get(current).unwrap() {
+                queue.push_back((neighbour, current, send));
+            }
+        }
+    }
+
+    fn pulse_count(&mut self) -> usize {
+        let mut low = 0;
+        let mut high = 0;
+
+        for _ in 0..1000 {
+            self.send("broadcaster", Pulse::Low, |_, _, pulse| match pulse {
+                Pulse::Low => low += 1,
+                Pulse::High => high += 1,
+            });
+        }
+        low * high
+    }
+
+    fn when_rx_goes_low(&mut self) -> usize {
+        let rx_sources: Vec<_> = self
+            .connections
+            .iter()
+            .filter_map(|(r, v)| if v.contains(&"rx") { Some(r) } else { None })
+            .collect();
+        assert!(rx_sources.len() == 1);
+        let rx_source = *rx_sources[0];
+        assert!(self.modules.get(rx_source) == Some(&Module::Conjunction));
+
+        let mut steps: HashMap<&str, usize> = self
+            .connections
+            .iter()
+            .filter_map(|(&r, v)| {
+                if v.contains(&rx_source) {
+                    Some((r, 0))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        assert!(steps
+            .keys()
+            .all(|source| self.modules.get(source) == Some(&Module::Conjunction)));
+
+        let mut step = 0;
+        loop {
+            step += 1;
+            self.send("broadcaster", Pulse::Low, |to, from, pulse| {
+                if to == rx_source && pulse == Pulse::High {
+                    // This is the cycle for the module `from`
+                    steps.entry(from).and_modify(|s| *s = step);
+                }
+            });
+
+            // We have all cycles, time to maths
+            if steps.values().all(|&v| v > 0) {
+                break;
+            }
+        }
+        // Each source first fires at its period, and again every period after
+        Periodic::combine_all(steps.values().map(|&step| Periodic::new(step, step)))
+            .unwrap()
+            .offset
+    }
+}
+
+pub fn parse(input: &str) -> Result<Network<'_>, ParseError> {
+    Network::new(input)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(parse(input).reported()?.pulse_count())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(parse(input).reported()?.when_rx_goes_low())
+}
+
+/// A random network shaped like the real ones. The broadcaster drives four counters, each a
+/// chain of `size` flip-flops (from 5 to 15) and a conjunction that fires and resets them at
+/// a different prime. Every counter then sends a high pulse on towards `rx`.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let bits = size.clamp(5, 15);
+    let mut periods: Vec<u64> = ((1 << (bits - 1))..(1 << bits))
+        .filter(|&n| is_prime(n))
+        .collect();
+    rng.shuffle(&mut periods);
+
+    let mut names = rng.names(4 * (bits + 2) + 2, 2);
+    names.retain(|name| name != "rx");
+    let mut names = names.into_iter();
+    let last = names.next().unwrap();
+
+    let mut lines = vec![];
+    let mut starts = vec![];
+    for &period in &periods[..4] {
+        let flops: Vec<String> = names.by_ref().take(bits).collect();
+        let (hub, inverter) = (names.next().unwrap(), names.next().unwrap());
+        let mut resets = vec![inverter.clone()];
+        for (bit, flop) in flops.iter().enumerate() {
+            let mut targets: Vec<&str> =
+                flops.get(bit + 1).map(String::as_str).into_iter().collect();
+            // The hub watches the bits that are set in the period, and sets the rest so
+            // the carry from the lowest bit wraps the whole counter back round to zero
+            if period >> bit & 1 == 1 {
+                targets.push(&hub);
+            }
+            if bit == 0 || period >> bit & 1 == 0 {
+                resets.push(flop.clone());
+            }
+            rng.shuffle(&mut targets);
+            lines.push(format!("%{flop} -> {}", targets.join(", ")));
+        }
+        rng.shuffle(&mut resets);
+        lines.push(format!("&{hub} -> {}", resets.join(", ")));
+        lines.push(format!("&{inverter} -> {last}"));
+        starts.push(flops[0].clone());
+    }
+    lines.push(format!("&{last} -> rx"));
+    lines.push(format!("broadcaster -> {}", starts.join(", ")));
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 32_000_000), (1, "-2", 11_687_500));
+
+    #[test]
+    fn test_generate() {
+        for size in [5, 8, 12] {
+            let input = generate(size, &mut Rng::new(20));
+            assert!(part_one(&input).unwrap() > 0);
+
+            // Presses until `rx` gets a low pulse, which is four different primes multiplied
+            let mut presses = part_two(&input).unwrap() as u64;
+            let mut primes = vec![];
+            for p in (1 << (size - 1))..(1 << size) {
+                if presses.is_multiple_of(p) {
+                    assert!(is_prime(p));
+                    primes.push(p);
+                    presses /= p;
+                }
+            }
+            assert_eq!((primes.len(), presses), (4, 1));
+        }
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+
+use crate::grid::Grid;
+use crate::numeric::{lagrange, Rational};
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use crate::visualize::{Colour, Frame};
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(21);
+
+#[derive(Debug)]
+pub struct Garden {
+    walls: Grid<bool>,
+    width: i32,
+    start: (i32, i32),
+}
+
+impl Garden {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        let garden = Grid::try_parse(s, |b| matches!(b, b'.' | b'#' | b'S').then_some(b))?;
+        let start = garden
+            .find(|&b| b == b'S')
+            .ok_or_else(|| Span::new(s).error("expected a start, S"))?;
+        Ok(Self {
+            walls: garden.map(|&b| b == b'#'),
+            start: (start.0 as i32, start.1 as i32),
+            width: garden.width() as i32,
+        })
+    }
+
+    /// The plots that can be reached with one more step than `plots`.
+    fn step(&self, plots: &HashSet<(i32, i32)>) -> HashSet<(i32, i32)> {
+        plots
+            .iter()
+            .flat_map(|&(r, c)| [(r + 1, c), (r, c + 1), (r - 1, c), (r, c - 1)])
+            .filter(|&(r, c)| !*self.walls.get_wrapping(r as isize, c as isize))
+            .collect()
+    }
+
+    fn steps(&self, count: usize) -> usize {
+        let mut steps_next: HashSet<(i32, i32)> = HashSet::new();
+        let mut steps: HashSet<(i32, i32)> = HashSet::new();
+        steps.insert(self.start);
+
+        for _ in 0..count {
+            steps_next.clear();
+            for (r, c) in steps.drain() {
+                for next in [(r + 1, c), (r, c + 1), (r - 1, c), (r, c - 1)] {
+                    if *self.walls.get_wrapping(next.0 as isize, next.1 as isize) {
+                        continue;
+                    }
+                    steps_next.insert(next);
+                }
+            }
+            (steps, steps_next) = (steps_next, steps);
+        }
+        steps.len()
+    }
+
+    fn many_steps(&self, counts: &[usize]) -> Vec<isize> {
+        let mut results = Vec::new();
+        let mut steps_next: HashSet<(i32, i32)> = HashSet::new();
+        let mut steps: HashSet<(i32, i32)> = HashSet::new();
+        steps.insert(self.start);
+
+        let mut iteration = 0;
+        'counting: for count in counts {
+            loop {
+                iteration += 1;
+                steps_next.clear();
+                for (r, c) in steps.drain() {
+                    for next in [(r + 1, c), (r, c + 1), (r - 1, c), (r, c - 1)] {
+                        if *self.walls.get_wrapping(next.0 as isize, next.1 as isize) {
+                            continue;
+                        }
+                        steps_next.insert(next);
+                    }
+                }
+                (steps, steps_next) = (steps_next, steps);
+                if *count == iteration {
+                    results.push(steps.len() as isize);
+                    continue 'counting;
+                }
+            }
+        }
+
+        results
+    }
+}
+
+pub fn parse(input: &str) -> Result<Garden, ParseError> {
+    Garden::new(input)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(parse(input).reported()?.steps(64))
+}
+
+pub fn part_one_many_steps(input: &str) -> Option<usize> {
+    Some(parse(input).reported()?.many_steps(&[64])[0] as usize)
+}
+
+impl Garden {
+    /// The garden repeats every `width` steps, and after the first `remainder` steps the
+    /// reachable area grows quadratically with each repeat, so three samples fit the curve.
+    fn steps_quadratic(&self, steps: usize) -> usize {
+        let width = self.width as usize;
+        let remainder = steps % width;
+
+        assert!(remainder == width / 2);
+
+        let counts: Vec<usize> = (0..=2).map(|i| remainder + i * width).collect();
+        let points: Vec<(i128, i128)> = counts
+            .iter()
+            .zip(self.many_steps(&counts))
+            .map(|(&count, reached)| (count as i128, reached as i128))
+            .collect();
+
+        let reached = lagrange(&points, steps as i128)
+            .and_then(Rational::to_integer)
+            .expect("quadratic fits the samples");
+        reached as usize
+    }
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(parse(input).reported()?.steps_quadratic(26_501_365))
+}
+
+/// A garden `width` plots across, with `rocks` rocks scattered away from the middle row and
+/// column and the edges, which have to stay clear for the reachable plots to grow quadratically.
+fn random_garden(width: usize, rocks: usize, rng: &mut Rng) -> String {
+    let middle = width / 2;
+    let mut plots = vec![vec!['.'; width]; width];
+    let free = (width - 3) * (width - 3);
+    for _ in 0..rocks.min(free / 2) {
+        loop {
+            let (row, column) = (
+                rng.between(1, width as i64 - 2),
+                rng.between(1, width as i64 - 2),
+            );
+            let (row, column) = (row as usize, column as usize);
+            if row != middle && column != middle && plots[row][column] == '.' {
+                plots[row][column] = '#';
+                break;
+            }
+        }
+    }
+    plots[middle][middle] = 'S';
+    plots
+        .iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// A garden 131 plots across like the real ones, as part two only works when the steps leave
+/// the start half way across a garden, with `size` rocks scattered over it.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    random_garden(131, size, rng)
+}
+
+/// The plots reached after each step, for the 64 steps of part one, or on a 5 by 5 tiling
+/// of the garden for the steps part two samples.
+pub fn visualize(input: &str, part: u8) -> impl Iterator<Item = Frame> {
+    parse(input)
+        .reported()
+        .into_iter()
+        .flat_map(move |garden| frames(garden, part))
+}
+
+fn frames(garden: Garden, part: u8) -> impl Iterator<Item = Frame> {
+    let width = garden.width;
+    let (tiles, steps) = if part == 1 {
+        (1, 64)
+    } else {
+        (5, width / 2 + 2 * width)
+    };
+    let offset = tiles / 2 * width;
+
+    let size = (tiles * width) as usize;
+    let mut base = Frame::new(size, size);
+    for row in 0..size {
+        for column in 0..size {
+            let wall = *garden.walls.get_wrapping(row as isize, column as isize);
+            base.draw(
+                (row, column),
+                if wall { '#' } else { '·' },
+                Some(Colour::Grey),
+            );
+        }
+    }
+
+    let mut plots = HashSet::from([garden.start]);
+    (0..=steps).map(move |step| {
+        if step > 0 {
+            plots = garden.step(&plots);
+        }
+        let mut frame = base.clone();
+        for &(r, c) in &plots {
+            let (row, column) = (r + offset, c + offset);
+            if row >= 0 && column >= 0 {
+                frame.draw((row as usize, column as usize), 'O', Some(Colour::Green));
+            }
+        }
+        frame.with_caption(format!("{step} steps, {} plots", plots.len()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_part_one() {
+        let garden = Garden::new(&crate::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(garden.steps(6), 16);
+    }
+
+    #[test]
+    fn test_many_steps() {
+        let garden = Garden::new(&crate::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(garden.many_steps(&[6, 10, 50]), vec![16, 50, 1594]);
+    }
+
+    #[test_case(6 => 16)]
+    #[test_case(10 => 50)]
+    #[test_case(50 => 1594)]
+    fn test_part_two(steps: usize) -> usize {
+        let garden = Garden::new(&crate::template::read_file("examples", DAY)).unwrap();
+        garden.steps(steps)
+    }
+
+    #[test]
+    fn test_visualize() {
+        let input = crate::template::read_file("examples", DAY);
+        let frame = visualize(&input, 1).nth(6).unwrap();
+        assert_eq!(frame.caption(), "6 steps, 16 plots");
+        assert_eq!(frame.to_string().lines().nth(4), Some("·O#O#O·O#··"));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(2000, &mut Rng::new(21));
+        assert_eq!(input.matches('#').count(), 2000);
+        assert!(part_one(&input).unwrap() > 0);
+    }
+
+    #[test]
+    fn test_quadratic_matches_stepping() {
+        for seed in 0..3 {
+            let garden = parse(&random_garden(21, 60, &mut Rng::new(seed))).unwrap();
+            let steps = 10 + 3 * 21;
+            assert_eq!(garden.steps_quadratic(steps), garden.steps(steps));
+        }
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
@@ -0,0 +1,444 @@
+use crate::image::{Image, Rgb};
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Write};
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(22);
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Point3D {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+impl Point3D {
+    fn new(s: Span) -> Result<Self, ParseError> {
+        let [x, y, z] = s.array(s.split(","))?;
+        Ok(Self {
+            x: x.parse()?,
+            y: y.parse()?,
+            z: z.parse()?,
+        })
+    }
+}
+
+impl PartialOrd for Point3D {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Point3D {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.z.cmp(&other.z)
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Brick {
+    bounds: (Point3D, Point3D),
+    supports: HashSet<usize>,
+    supported_by: HashSet<usize>,
+}
+
+impl Brick {
+    fn new(s: Span) -> Result<Self, ParseError> {
+        let (first, second) = s.split_once("~")?;
+        let (first, second) = (Point3D::new(first)?, Point3D::new(second)?);
+        let bounds = if first <= second {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        Ok(Self {
+            bounds,
+            ..Default::default()
+        })
+    }
+
+    fn covers(&self) -> Vec<(i32, i32)> {
+        let (x1, x2) = if self.bounds.0.x < self.bounds.1.x {
+            (self.bounds.0.x, self.bounds.1.x)
+        } else {
+            (self.bounds.1.x, self.bounds.0.x)
+        };
+        let (y1, y2) = if self.bounds.0.y < self.bounds.1.y {
+            (self.bounds.0.y, self.bounds.1.y)
+        } else {
+            (self.bounds.1.y, self.bounds.0.y)
+        };
+        (x1..=x2).cartesian_product(y1..=y2).collect()
+    }
+
+    fn height(&self) -> i32 {
+        self.bounds.1.z - self.bounds.0.z + 1
+    }
+}
+
+#[derive(Debug)]
+pub struct Pile {
+    bricks: Vec<Brick>,
+}
+
+impl Pile {
+    fn new(s: &str) -> Result<Self, ParseError> {
+        let bricks: Vec<Brick> = Span::new(s)
+            .lines()
+            .map(Brick::new)
+            .collect::<Result<_, _>>()?;
+        let bricks = bricks
+            .into_iter()
+            .sorted_by(|a, b| a.bounds.cmp(&b.bounds))
+            .collect();
+        Ok(Self { bricks })
+    }
+
+    /// Lets every brick fall as far as it can, updating its bounds to where it comes to rest.
+    fn drop(&mut self) {
+        let mut heights: HashMap<(i32, i32), i32> = HashMap::new();
+        let mut occupies: HashMap<(i32, i32), usize> = HashMap::new();
+        let mut supports: HashMap<usize, HashSet<usize>> = HashMap::new();
+        for (idx, brick) in self.bricks.iter_mut().enumerate() {
+            let base = *brick
+                .covers()
+                .iter()
+                .filter_map(|point| heights.get(point))
+                .max()
+                .unwrap_or(&0);
+
+            let top = base + brick.height();
+            brick.bounds.0.z = base + 1;
+            brick.bounds.1.z = top;
+            for (x, y) in brick.covers() {
+                if let Some(height) = heights.insert((x, y), top) {
+                    if height == base {
+                        let other = *occupies.get(&(x, y)).unwrap();
+
+                        brick.supported_by.insert(other);
+                        // Avoid mulitiple mutable borrows from:
+                        //     self.bricks[other].supports.insert(idx);
+                        // Build up another variable and zoop it across after the brick loop
+                        supports
+                            .entry(other)
+                            .and_modify(|s| {
+                                s.insert(idx);
+                            })
+                            .or_insert_with(|| HashSet::from([idx]));
+                    }
+                }
+                occupies.insert((x, y), idx);
+            }
+        }
+
+        for (idx, set) in supports {
+            self.bricks[idx].supports.extend(set);
+        }
+    }
+
+    fn safely_removable(&self) -> usize {
+        self.bricks
+            .iter()
+            .filter(|brick| {
+                brick
+                    .supports
+                    .iter()
+                    .all(|other| self.bricks[*other].supported_by.len() > 1)
+            })
+            .count()
+    }
+
+    fn tumble_sum(&self) -> usize {
+        let mut result = 0;
+
+        for (idx, brick) in self.bricks.iter().enumerate() {
+            if brick.supports.is_empty() {
+                continue;
+            }
+            let mut fallen: HashSet<usize> = HashSet::new();
+            let mut queue: VecDeque<usize> = VecDeque::new();
+
+            queue.push_back(idx);
+
+            while let Some(idx) = queue.pop_front() {
+                fallen.insert(idx);
+
+                for &supported in &self.bricks[idx].supports {
+                    if self.bricks[supported]
+                        .supported_by
+                        .iter()
+                        .all(|support| fallen.contains(support))
+                    {
+                        queue.push_back(supported);
+                    }
+                }
+            }
+
+            result += fallen.len() - 1;
+        }
+        result
+    }
+}
+
+/// The pile seen from the front, with x across, then from the side, with y across, as in
+/// the puzzle. Bricks are lettered from `A`, or `#` after `Z`, and `?` is where more than one
+/// brick can be seen.
+impl Display for Pile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        type Axis = fn(&Point3D) -> i32;
+        let top = self.bricks.iter().map(|b| b.bounds.1.z).max().unwrap_or(0);
+        let views: [(&str, Axis); 2] = [("x", |p| p.x), ("y", |p| p.y)];
+        for (i, (name, across)) in views.into_iter().enumerate() {
+            let ends = |brick: &Brick| {
+                let (a, b) = (across(&brick.bounds.0), across(&brick.bounds.1));
+                a.min(b)..=a.max(b)
+            };
+            let first = self
+                .bricks
+                .iter()
+                .map(|b| *ends(b).start())
+                .min()
+                .unwrap_or(0);
+            let last = self
+                .bricks
+                .iter()
+                .map(|b| *ends(b).end())
+                .max()
+                .unwrap_or(0);
+            let columns = first.min(0)..=last;
+
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{name}")?;
+            for z in (1..=top).rev() {
+                for column in columns.clone() {
+                    let mut seen = self.bricks.iter().enumerate().filter(|(_, brick)| {
+                        (brick.bounds.0.z..=brick.bounds.1.z).contains(&z)
+                            && ends(brick).contains(&column)
+                    });
+                    let symbol = match (seen.next(), seen.next()) {
+                        (None, _) => '.',
+                        (Some(_), Some(_)) => '?',
+                        (Some((idx, _)), None) => u8::try_from(idx)
+                            .ok()
+                            .filter(|&idx| idx < 26)
+                            .map_or('#', |idx| char::from(b'A' + idx)),
+                    };
+                    write!(f, "{symbol}")?;
+                }
+                writeln!(f, " {z}")?;
+            }
+            writeln!(f, "{} 0", "-".repeat(columns.count()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Colours for the bricks, one after another.
+const BRICK_COLOURS: [Rgb; 8] = [
+    [230, 80, 70],
+    [240, 170, 50],
+    [230, 220, 80],
+    [100, 200, 90],
+    [70, 190, 200],
+    [80, 120, 230],
+    [160, 100, 220],
+    [220, 110, 180],
+];
+
+fn shaded([r, g, b]: Rgb, factor: f64) -> Rgb {
+    [r, g, b].map(|c| (f64::from(c) * factor) as u8)
+}
+
+/// The settled pile seen from the front, with x across, and from the side, with y across.
+/// Bricks further away are darker, and nearer ones hide them.
+pub fn render(input: &str) -> Vec<Image> {
+    const SCALE: usize = 4;
+    const BACKGROUND: Rgb = [20, 20, 30];
+    let Some(mut pile) = parse(input).reported() else {
+        return Vec::new();
+    };
+    pile.drop();
+
+    type Axis = fn(&Point3D) -> i32;
+    let extent = |axis: Axis| {
+        pile.bricks
+            .iter()
+            .map(|b| axis(&b.bounds.0).max(axis(&b.bounds.1)))
+            .max()
+            .unwrap_or(0)
+    };
+    let top = extent(|p| p.z) as usize;
+    let x_width = extent(|p| p.x) as usize + 1;
+    let y_width = extent(|p| p.y) as usize + 1;
+
+    let height = (top + 1) * SCALE;
+    let mut image = Image::new((x_width + 1 + y_width) * SCALE, height, BACKGROUND);
+    let panels: [(usize, usize, Axis, Axis); 2] = [
+        (0, x_width, |p| p.x, |p| p.y),
+        ((x_width + 1) * SCALE, y_width, |p| p.y, |p| p.x),
+    ];
+    for (left, width, across, depth) in panels {
+        let deepest = extent(depth);
+        // Paint the furthest bricks first, so that the nearest end up on top
+        let order = (0..pile.bricks.len()).sorted_by_key(|&i| {
+            let bounds = &pile.bricks[i].bounds;
+            -depth(&bounds.0).min(depth(&bounds.1))
+        });
+        for idx in order {
+            let (a, b) = &pile.bricks[idx].bounds;
+            let near = depth(a).min(depth(b));
+            let colour = shaded(
+                BRICK_COLOURS[idx % BRICK_COLOURS.len()],
+                1.0 - 0.6 * f64::from(near) / f64::from(deepest.max(1)),
+            );
+            let (first, last) = (
+                across(a).min(across(b)) as usize,
+                across(a).max(across(b)) as usize,
+            );
+            let corner = ((top - b.z as usize) * SCALE, left + first * SCALE);
+            let size = ((b.z - a.z + 1) as usize * SCALE, (last - first + 1) * SCALE);
+            image.fill(corner, size, shaded(colour, 0.5));
+            image.fill(
+                (corner.0 + 1, corner.1 + 1),
+                (size.0 - 2, size.1 - 2),
+                colour,
+            );
+        }
+        image.fill((height - SCALE, left), (SCALE, width * SCALE), [90, 90, 90]);
+    }
+    vec![image]
+}
+
+pub fn parse(input: &str) -> Result<Pile, ParseError> {
+    Pile::new(input)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let mut pile = parse(input).reported()?;
+    pile.drop();
+
+    Some(pile.safely_removable())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let mut pile = parse(input).reported()?;
+    pile.drop();
+
+    Some(pile.tumble_sum())
+}
+
+/// `size` random bricks up to five cubes long, in a ten by ten column like the real snapshot.
+/// They start out spread over a tall stack with gaps between them to fall into.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let top = size as i64 / 2 + 5;
+    let mut filled: HashSet<(i64, i64, i64)> = HashSet::new();
+    let mut input = String::new();
+    let mut placed = 0;
+    while placed < size {
+        let start = [rng.between(0, 9), rng.between(0, 9), rng.between(1, top)];
+        let mut end = start;
+        let axis = rng.index(3);
+        end[axis] += rng.between(0, 4);
+        if end[0] > 9 || end[1] > 9 {
+            continue;
+        }
+        let cubes: Vec<_> = (start[0]..=end[0])
+            .cartesian_product(start[1]..=end[1])
+            .cartesian_product(start[2]..=end[2])
+            .map(|((x, y), z)| (x, y, z))
+            .collect();
+        if cubes.iter().any(|cube| filled.contains(cube)) {
+            continue;
+        }
+        filled.extend(cubes);
+        placed += 1;
+        let ([x1, y1, z1], [x2, y2, z2]) = (start, end);
+        writeln!(input, "{x1},{y1},{z1}~{x2},{y2},{z2}").unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 5), (2, "", 7));
+
+    #[test]
+    fn test_brick_new() {
+        let result = Brick::new(Span::new("2,2,2~2,2,2")).unwrap();
+        assert_eq!(
+            result,
+            Brick {
+                bounds: (Point3D { x: 2, y: 2, z: 2 }, Point3D { x: 2, y: 2, z: 2 }),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_brick_height() {
+        let result = Brick::new(Span::new("2,2,2~2,2,2")).unwrap().height();
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn test_brick_covers() {
+        let result = Brick::new(Span::new("2,2,2~2,2,2")).unwrap().covers();
+        assert_eq!(result, vec![(2, 2)]);
+    }
+
+    #[test]
+    fn test_drop_settles() {
+        let mut pile = Pile::new(&crate::template::read_file("examples", DAY)).unwrap();
+        pile.drop();
+        let heights = pile
+            .bricks
+            .iter()
+            .map(|brick| (brick.bounds.0.z, brick.bounds.1.z))
+            .collect_vec();
+        assert_eq!(
+            heights,
+            [(1, 1), (2, 2), (2, 2), (3, 3), (3, 3), (4, 4), (5, 6)]
+        );
+        crate::assert_snapshot!("day22_settled", pile);
+    }
+
+    #[test]
+    fn test_render() {
+        let image = &render(&crate::template::read_file("examples", DAY))[0];
+        assert_eq!((image.width(), image.height()), (28, 28));
+        // The tall brick at the top, nothing beside it, and the ground
+        assert_ne!(image.get((2, 6)), Some([20, 20, 30]));
+        assert_eq!(image.get((2, 2)), Some([20, 20, 30]));
+        assert_eq!(image.get((26, 20)), Some([90, 90, 90]));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(300, &mut Rng::new(22));
+        let mut pile = parse(&input).unwrap();
+        assert_eq!(pile.bricks.len(), 300);
+        pile.drop();
+        // Everything comes to rest on the ground or on another brick
+        assert!(pile
+            .bricks
+            .iter()
+            .all(|brick| brick.bounds.0.z == 1 || !brick.supported_by.is_empty()));
+        assert!(part_one(&input).unwrap() <= 300);
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
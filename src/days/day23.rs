@@ -0,0 +1,259 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::geometry::{Direction, Point};
+use crate::graph::Graph;
+use crate::grid::Grid;
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(23);
+
+#[derive(Debug)]
+pub enum Cell {
+    Path,
+    Forest,
+    Slope(Direction),
+}
+
+impl Cell {
+    fn new(b: u8) -> Option<Self> {
+        use Cell::*;
+        match b {
+            b'#' => Some(Forest),
+            b'.' => Some(Path),
+            b'^' | b'>' | b'v' | b'<' => Direction::try_from(b).ok().map(Slope),
+            _ => None,
+        }
+    }
+}
+
+fn neighbours(
+    maze: &Grid<Cell>,
+    position: Point<usize>,
+    slippy: bool,
+) -> impl Iterator<Item = Point<usize>> + '_ {
+    use Cell::*;
+    maze.neighbours4(position.into())
+        .map(Point::from)
+        .filter(move |&next| match &maze[next] {
+            Forest => false,
+            // slopes can't be climbed
+            Slope(d) if slippy => next.checked_step(*d) != Some(position),
+            _ => true,
+        })
+}
+
+fn build_graph(maze: &Grid<Cell>, slippy: bool) -> Graph<Point<usize>> {
+    let mut graph = Graph::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::from_iter([Point::new(0, 1)]);
+    graph.intern(Point::new(0, 1));
+
+    while let Some(current) = queue.pop_front() {
+        for neighbour in neighbours(maze, current, slippy) {
+            graph.add_edge(current, neighbour, 1);
+
+            if visited.insert(neighbour) {
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    graph
+}
+
+/// The map, which has to have a path in from the top left and out to the bottom right.
+pub fn parse(input: &str) -> Result<Grid<Cell>, ParseError> {
+    let maze = Grid::try_parse(input, Cell::new)?;
+    let (height, width) = (maze.height(), maze.width());
+    if width < 2 || !matches!(maze[(0, 1)], Cell::Path) {
+        return Err(Span::new(input).error("expected a path in at the top left"));
+    }
+    if !matches!(maze[(height - 1, width - 2)], Cell::Path) {
+        return Err(ParseError {
+            line: height,
+            column: width - 1,
+            message: "expected a path out at the bottom right".to_string(),
+        });
+    }
+    Ok(maze)
+}
+
+fn longest_path(graph: &Graph<Point<usize>>, maze: &Grid<Cell>) -> usize {
+    let start = graph.id(&Point::new(0, 1)).unwrap();
+    let goal = graph
+        .id(&Point::new(maze.height() - 1, maze.width() - 2))
+        .expect("goal should be reachable");
+    graph.longest_path(start, goal).unwrap()
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let maze = parse(input).reported()?;
+    let graph = build_graph(&maze, true);
+    Some(longest_path(&graph, &maze))
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let maze = parse(input).reported()?;
+    let graph = build_graph(&maze, false).contract_degree_two();
+    Some(longest_path(&graph, &maze))
+}
+
+/// A random map like the real ones: a `size` by `size` lattice of junctions, at most six
+/// as part two tries every path, joined by corridors of random lengths. Slopes on either end
+/// of each corridor only go east or south, so part one can't go round in circles.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let n = size.clamp(2, 6);
+    let lines = |first: usize, rng: &mut Rng| {
+        let mut lines = vec![first];
+        for _ in 1..n {
+            lines.push(lines.last().unwrap() + rng.between(4, 12) as usize);
+        }
+        lines
+    };
+    let rows = lines(rng.between(2, 6) as usize, rng);
+    let columns = lines(1, rng);
+    let height = rows[n - 1] + rng.between(3, 6) as usize;
+    let width = columns[n - 1] + 2;
+
+    let mut links: Vec<((usize, usize), (usize, usize))> = (0..n)
+        .cartesian_product(0..n)
+        .flat_map(|(i, j)| [((i, j), (i + 1, j)), ((i, j), (i, j + 1))])
+        .filter(|&(_, (i, j))| i < n && j < n)
+        .collect();
+    rng.shuffle(&mut links);
+
+    // Each junction keeps at least one way in and one way out, so none are cut off
+    let mut outs: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut ins: HashMap<(usize, usize), usize> = HashMap::new();
+    for &(from, to) in &links {
+        *outs.entry(from).or_default() += 1;
+        *ins.entry(to).or_default() += 1;
+    }
+    links.retain(|&(from, to)| {
+        let keep = outs[&from] == 1 || ins[&to] == 1 || !rng.chance(0.2);
+        if !keep {
+            *outs.get_mut(&from).unwrap() -= 1;
+            *ins.get_mut(&to).unwrap() -= 1;
+        }
+        keep
+    });
+
+    let mut map = vec![vec!['#'; width]; height];
+    for row in &mut map[..rows[0]] {
+        row[1] = '.';
+    }
+    for row in &mut map[rows[n - 1]..] {
+        row[width - 2] = '.';
+    }
+    map[rows[0] - 1][1] = 'v';
+    map[rows[n - 1] + 1][width - 2] = 'v';
+    for ((i, j), (k, l)) in links {
+        let (from, to) = ((rows[i], columns[j]), (rows[k], columns[l]));
+        for row in &mut map[from.0..=to.0] {
+            row[from.1..=to.1].fill('.');
+        }
+        if i == k {
+            map[from.0][from.1 + 1] = '>';
+            map[to.0][to.1 - 1] = '>';
+        } else {
+            map[from.0 + 1][from.1] = 'v';
+            map[to.0 - 1][to.1] = 'v';
+        }
+    }
+    map.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+    use test_case::test_case;
+
+    crate::example_tests!((1, "", 94), (2, "", 154));
+
+    fn adjacency(
+        graph: &Graph<Point<usize>>,
+    ) -> HashMap<Point<usize>, HashMap<Point<usize>, usize>> {
+        (0..graph.len())
+            .map(|id| {
+                let edges = graph
+                    .neighbours(id)
+                    .map(|(to, weight)| (*graph.label(to), weight))
+                    .collect();
+                (*graph.label(id), edges)
+            })
+            .collect()
+    }
+
+    #[test_case(Point::new(0, 1) => vec![Point::new(1, 1)] ; "0,1: path south")]
+    #[test_case(Point::new(1, 1) => vec![Point::new(0, 1), Point::new(1, 2)] ; "1,1: paths east and north")]
+    #[test_case(Point::new(3, 3) => vec![Point::new(3, 4), Point::new(4, 3)]; "3,3: path east, slope south")]
+    #[test_case(Point::new(3, 11) => vec![Point::new(3, 12), Point::new(4, 11)] ; "3,11: slopes east, south, west (impassable)")]
+    #[test_case(Point::new(5, 3) => vec![Point::new(5, 4), Point::new(6, 3)] ; "5,3: slopes east, south, north (impassable)")]
+    fn test_slippy_neighbours(start: Point<usize>) -> Vec<Point<usize>> {
+        let maze = parse(&crate::template::read_file("examples", DAY)).unwrap();
+        neighbours(&maze, start, true).collect()
+    }
+
+    #[test_case(Point::new(0, 1) => vec![Point::new(1, 1)] ; "0,1: path south")]
+    #[test_case(Point::new(1, 1) => vec![Point::new(0, 1), Point::new(1, 2)] ; "1,1: paths east and north")]
+    #[test_case(Point::new(3, 3) => vec![Point::new(3, 4), Point::new(4, 3)]; "3,3: path east, slope south")]
+    #[test_case(Point::new(3, 11) => vec![Point::new(3, 10), Point::new(3, 12), Point::new(4, 11)] ; "3,11: slopes west, east, south")]
+    #[test_case(Point::new(5, 3) => vec![Point::new(4, 3), Point::new(5, 4), Point::new(6, 3)] ; "5,3: slopes north, east, south")]
+    fn test_dry_neighbours(start: Point<usize>) -> Vec<Point<usize>> {
+        let maze = parse(&crate::template::read_file("examples", DAY)).unwrap();
+        neighbours(&maze, start, false).collect()
+    }
+
+    #[test]
+    fn test_graph_collapse() {
+        let graph = build_graph(
+            &parse(&crate::template::read_file_part("examples", DAY, 2)).unwrap(),
+            false,
+        );
+        check!(
+            adjacency(&graph)
+                == HashMap::from([
+                    (Point::new(0, 1), HashMap::from([(Point::new(1, 1), 1)])),
+                    (
+                        Point::new(1, 1),
+                        HashMap::from([(Point::new(0, 1), 1), (Point::new(2, 1), 1)])
+                    ),
+                    (
+                        Point::new(2, 1),
+                        HashMap::from([(Point::new(1, 1), 1), (Point::new(3, 1), 1)])
+                    ),
+                    (Point::new(3, 1), HashMap::from([(Point::new(2, 1), 1)])),
+                ]),
+        );
+
+        check!(
+            adjacency(&graph.contract_degree_two())
+                == HashMap::from([
+                    (Point::new(0, 1), HashMap::from([(Point::new(3, 1), 3)])),
+                    (Point::new(3, 1), HashMap::from([(Point::new(0, 1), 3)])),
+                ]),
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..5 {
+            let input = generate(4, &mut Rng::new(seed));
+            let (one, two) = (part_one(&input).unwrap(), part_two(&input).unwrap());
+            assert!(one <= two);
+            assert!(one > input.lines().count());
+        }
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
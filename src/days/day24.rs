@@ -0,0 +1,350 @@
+use crate::numeric::{gcd, line_intersection, solve_linear, Rational};
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(24);
+
+/// A hailstone's path, looking only at the x and y axes.
+#[derive(Debug, PartialEq, Clone)]
+struct Line {
+    position: [i128; 2],
+    velocity: [i128; 2],
+}
+
+/// The position and velocity of a hailstone, as `[x, y, z, vx, vy, vz]`.
+fn hailstone(line: Span) -> Result<[i64; 6], ParseError> {
+    let (point, vector) = line.split_once("@")?;
+    let [x, y, z] = point.array(point.signed_numbers()?)?;
+    let [vx, vy, vz] = vector.array(vector.signed_numbers()?)?;
+    Ok([x, y, z, vx, vy, vz])
+}
+
+impl From<&Stone> for Line {
+    fn from(stone: &Stone) -> Self {
+        Self {
+            position: [stone.x, stone.y].map(i128::from),
+            velocity: [stone.vx, stone.vy].map(i128::from),
+        }
+    }
+}
+
+impl Line {
+    fn position_at(&self, time: Rational) -> [Rational; 2] {
+        [0, 1].map(|axis| {
+            Rational::integer(self.position[axis]) + time * Rational::integer(self.velocity[axis])
+        })
+    }
+
+    /// The times at which each of the paths reaches the place where they cross, which
+    /// may be in the past. `None` if they are parallel.
+    fn crossing_times(&self, other: &Self) -> Option<(Rational, Rational)> {
+        line_intersection(self.position, self.velocity, other.position, other.velocity)
+    }
+
+    fn intersect_in_future(&self, other: &Self) -> Option<[Rational; 2]> {
+        let (time, other_time) = self.crossing_times(other)?;
+        let now = Rational::integer(0);
+        (time >= now && other_time >= now).then(|| self.position_at(time))
+    }
+}
+
+fn crossing_in_zone(lines: &[Line], min: i128, max: i128) -> usize {
+    let zone = Rational::integer(min)..=Rational::integer(max);
+    lines
+        .iter()
+        .combinations(2)
+        .filter_map(|pair| pair[0].intersect_in_future(pair[1]))
+        .filter(|point| point.iter().all(|axis| zone.contains(axis)))
+        .count()
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let stones = parse(input).reported()?;
+    let lines: Vec<Line> = stones.iter().map(Line::from).collect();
+    Some(crossing_in_zone(
+        &lines,
+        200_000_000_000_000,
+        400_000_000_000_000,
+    ))
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Stone {
+    x: i64,
+    y: i64,
+    z: i64,
+    vx: i64,
+    vy: i64,
+    vz: i64,
+}
+
+impl Stone {
+    fn new(s: Span) -> Result<Self, ParseError> {
+        let [x, y, z, vx, vy, vz] = hailstone(s)?;
+        Ok(Self {
+            x,
+            y,
+            z,
+            vx,
+            vy,
+            vz,
+        })
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Stone>, ParseError> {
+    Span::new(input).lines().map(Stone::new).collect()
+}
+
+type Vector = [i128; 3];
+
+impl Stone {
+    fn position(&self) -> Vector {
+        [self.x, self.y, self.z].map(i128::from)
+    }
+
+    fn velocity(&self) -> Vector {
+        [self.vx, self.vy, self.vz].map(i128::from)
+    }
+}
+
+fn cross(a: Vector, b: Vector) -> Vector {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn minus(a: Vector, b: Vector) -> Vector {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// Where the rock has to start, and how fast it has to go, to hit every hailstone.
+fn throw(stones: &[Stone]) -> Option<(Vector, Vector)> {
+    // Watching from the first hailstone, it sits still at the origin, so the rock has to
+    // pass through the origin
+    let (first, others) = stones.split_first()?;
+    let others: Vec<(Vector, Vector)> = others
+        .iter()
+        .map(|stone| {
+            (
+                minus(stone.position(), first.position()),
+                minus(stone.velocity(), first.velocity()),
+            )
+        })
+        .collect();
+
+    // To also hit another hailstone, the rock's path has to lie in the plane through the
+    // origin and that hailstone's path. Two of those planes meet along the rock's path.
+    let direction = others
+        .iter()
+        .tuple_combinations()
+        .map(|(&(p1, v1), &(p2, v2))| cross(cross(p1, v1), cross(p2, v2)))
+        .find(|&direction| direction != [0; 3])?;
+    let divisor = direction.into_iter().reduce(gcd)?;
+    let direction = direction.map(|n| n / divisor);
+
+    // So the rock starts at -start * direction and moves at speed * direction. Hitting the
+    // hailstone at position p with velocity v then needs
+    //   (-start * direction) x v + p x (speed * direction) = p x v
+    // which is linear in start and speed, and small enough for a couple of hailstones.
+    let mut rows = Vec::new();
+    let mut rhs = Vec::new();
+    for &(position, velocity) in others.iter().take(2) {
+        let starts = cross(direction, velocity);
+        let speeds = cross(position, direction);
+        let targets = cross(position, velocity);
+        for axis in 0..3 {
+            rows.push(vec![-starts[axis], speeds[axis]]);
+            rhs.push(targets[axis]);
+        }
+    }
+    let [start, speed] = solve_linear(&rows, &rhs)?[..] else {
+        return None;
+    };
+    let [start, speed] = [start.to_integer()?, speed.to_integer()?];
+
+    // And back to where we were watching from
+    let (origin, drift) = (first.position(), first.velocity());
+    Some((
+        [0, 1, 2].map(|axis| origin[axis] - start * direction[axis]),
+        [0, 1, 2].map(|axis| drift[axis] + speed * direction[axis]),
+    ))
+}
+
+pub fn part_two(input: &str) -> Option<i64> {
+    let stones = parse(input).reported()?;
+    let (position, _) = throw(&stones)?;
+    position.into_iter().sum::<i128>().try_into().ok()
+}
+
+/// `size` random hailstones, at least three, with numbers about as big as the real ones.
+/// They are made by throwing a random rock and putting a hailstone wherever it will be at a
+/// few random times, so there is always a throw that hits them all.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let rock = [(); 3].map(|()| rng.between(100_000_000_000_000, 400_000_000_000_000));
+    let speed = [(); 3].map(|()| rng.between(-300, 300));
+    let mut times = HashSet::new();
+    let mut input = String::new();
+    while times.len() < size.max(3) {
+        let time = rng.between(10_000_000_000, 1_000_000_000_000);
+        let velocity = [(); 3].map(|()| rng.between(-300, 300));
+        if velocity == speed || !times.insert(time) {
+            continue;
+        }
+        let [x, y, z] = [0, 1, 2].map(|axis| rock[axis] + time * (speed[axis] - velocity[axis]));
+        let [vx, vy, vz] = velocity;
+        writeln!(input, "{x}, {y}, {z} @ {vx}, {vy}, {vz}").unwrap();
+    }
+    input
+}
+
+#[cfg(feature = "z3")]
+use z3::ast::{Ast, Int};
+#[cfg(feature = "z3")]
+use z3::{Config, Context, Solver};
+
+/// The same as [`part_two`], but letting Z3 find the rock. Needs the `z3` feature.
+#[cfg(feature = "z3")]
+pub fn part_two_z3(input: &str) -> Option<i64> {
+    let config = Config::new();
+    let context = Context::new(&config);
+    let solver = Solver::new(&context);
+
+    // The rock
+    let x = Int::new_const(&context, "x");
+    let y = Int::new_const(&context, "y");
+    let z = Int::new_const(&context, "z");
+    let vx = Int::new_const(&context, "vx");
+    let vy = Int::new_const(&context, "vy");
+    let vz = Int::new_const(&context, "vz");
+
+    let stones = parse(input).reported()?;
+    for stone in stones {
+        let sx = Int::from_i64(&context, stone.x);
+        let sy = Int::from_i64(&context, stone.y);
+        let sz = Int::from_i64(&context, stone.z);
+        let svx = Int::from_i64(&context, stone.vx);
+        let svy = Int::from_i64(&context, stone.vy);
+        let svz = Int::from_i64(&context, stone.vz);
+        let t = Int::fresh_const(&context, "t");
+
+        solver.assert(&(&sx + &svx * &t)._eq(&(&x + &vx * &t)));
+        solver.assert(&(&sy + &svy * &t)._eq(&(&y + &vy * &t)));
+        solver.assert(&(&sz + &svz * &t)._eq(&(&z + &vz * &t)));
+    }
+
+    solver.check();
+
+    let model = solver.get_model().unwrap();
+    let found_x = model.get_const_interp(&x).unwrap().as_i64().unwrap();
+    let found_y = model.get_const_interp(&y).unwrap().as_i64().unwrap();
+    let found_z = model.get_const_interp(&z).unwrap().as_i64().unwrap();
+
+    Some(found_x + found_y + found_z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    crate::example_tests!((2, "", 47));
+
+    fn line(s: &str) -> Line {
+        Line::from(&Stone::new(Span::new(s)).unwrap())
+    }
+
+    fn r(numerator: i128, denominator: i128) -> Rational {
+        Rational::new(numerator, denominator).unwrap()
+    }
+
+    #[test_case("0, 0, 30 @ 1, 1, -2" => Line { position: [0, 0], velocity: [1, 1] } ; "0,0 -> 1,1")]
+    #[test_case("19, 13, 30 @ -2, 1, -2" => Line { position: [19, 13], velocity: [-2, 1] } ; "First example")]
+    fn test_line_new(s: &str) -> Line {
+        line(s)
+    }
+
+    #[test]
+    fn test_line_intersect() {
+        let a = line("19, 13, 30 @ -2, 1, -2");
+        let b = line("12, 31, 28 @ -1, -2, -1");
+        let (time, _) = a.crossing_times(&b).unwrap();
+        assert_eq!(a.position_at(time), [r(31, 5), r(97, 5)]);
+    }
+
+    #[test]
+    fn test_line_in_future() {
+        let a = line("20, 25, 34 @ -2, -2, -4");
+        let b = line("20, 19, 15 @ 1, -5, -3");
+
+        let (time, _) = a.crossing_times(&b).unwrap();
+        assert_eq!(a.position_at(time), [19, 24].map(Rational::integer));
+
+        // a gets there in half a nanosecond, b was there a nanosecond ago
+        assert_eq!(a.crossing_times(&b), Some((r(1, 2), r(-1, 1))));
+        assert_eq!(a.intersect_in_future(&b), None);
+    }
+
+    #[test]
+    fn test_part_one() {
+        let stones = parse(&crate::template::read_file("examples", DAY)).unwrap();
+        let lines: Vec<Line> = stones.iter().map(Line::from).collect();
+        let result = crossing_in_zone(&lines, 7, 27);
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn test_throw() {
+        let stones = parse(&crate::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(throw(&stones), Some(([24, 13, 10], [-3, 1, 2])));
+    }
+
+    /// With numbers as big as in the real puzzle input, where floats would lose precision
+    #[test]
+    fn test_part_two_big_numbers() {
+        let input = "\
+449735412016520, -196510990366136, 103801347432374 @ -246, 323, -110
+297758394366803, 199055415401179, 130886352718367 @ 51, 78, 338
+459538101280844, 418878004580549, -74607809833396 @ -282, -372, 129
+172971107537888, -165518857651386, 86366150747784 @ 54, 298, -89
+";
+        assert_eq!(part_two(input), Some(732_605_827_295_000));
+    }
+
+    #[cfg(feature = "z3")]
+    #[test]
+    fn test_part_two_z3_agrees() {
+        let input = crate::template::read_file("examples", DAY);
+        let solved = part_two_z3(&input);
+        assert_eq!(solved, Some(47));
+        assert_eq!(solved, part_two(&input));
+    }
+
+    #[test]
+    fn test_generate() {
+        let stones = parse(&generate(50, &mut Rng::new(24))).unwrap();
+        assert_eq!(stones.len(), 50);
+        let (position, velocity) = throw(&stones).unwrap();
+        for stone in &stones {
+            // The rock and hailstone are in the same place at the same time, in the future
+            let gap = minus(stone.position(), position);
+            let closing = minus(velocity, stone.velocity());
+            let axis = (0..3).find(|&axis| closing[axis] != 0).unwrap();
+            let time = gap[axis] / closing[axis];
+            assert!(time > 0);
+            assert_eq!(gap, closing.map(|axis| axis * time));
+        }
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::graph::Graph;
+use crate::parse::{ParseError, Span};
+use crate::random::Rng;
+use crate::template::runner::Reported;
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// The current day.
+pub const DAY: crate::template::Day = crate::day!(25);
+
+pub fn parse(s: &str) -> Result<Graph<&str>, ParseError> {
+    let mut graph = Graph::new();
+    for line in Span::new(s).lines() {
+        let (node, adjacent) = line.split_once(": ")?;
+        for other in adjacent.split(" ") {
+            if other.as_str().is_empty() {
+                return Err(other.error("expected a component"));
+            }
+            graph.add_undirected_edge(node.as_str(), other.as_str(), 1);
+        }
+    }
+    Ok(graph)
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let graph = parse(input).reported()?;
+    let (wires, group) = graph.min_cut()?;
+    if wires != 3 {
+        return Err::<usize, _>(format!("the smallest cut is {wires} wires, not three")).reported();
+    }
+    Some(group.len() * (graph.len() - group.len()))
+}
+
+pub fn part_two(_input: &str) -> Option<String> {
+    Some("White Christmas".to_string())
+}
+
+/// `size` random components, at least twelve, in two groups that are each well wired up
+/// inside, with only three wires between them.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(12);
+    let names = rng.names(size, 3);
+    let split = rng.between(6, size as i64 - 6) as usize;
+    let mut wires: HashSet<(usize, usize)> = HashSet::new();
+    let mut connect = |a: usize, b: usize| wires.insert((a.min(b), a.max(b)));
+    for (low, high) in [(0, split), (split, size)] {
+        // A ring to hold the group together, and enough extra wires that no few of them
+        // cut it in two
+        for node in low..high {
+            connect(node, if node + 1 == high { low } else { node + 1 });
+            let mut others: Vec<usize> = (low..high).filter(|&other| other != node).collect();
+            rng.shuffle(&mut others);
+            for &other in &others[..4] {
+                connect(node, other);
+            }
+        }
+    }
+    let inside = wires.len();
+    while wires.len() < inside + 3 {
+        wires.insert((rng.index(split), split + rng.index(size - split)));
+    }
+
+    // Each wire is listed once, from whichever end
+    let mut wires: Vec<(usize, usize)> = wires
+        .into_iter()
+        .sorted()
+        .map(|(a, b)| if rng.chance(0.5) { (a, b) } else { (b, a) })
+        .collect();
+    wires.sort_unstable();
+    let mut input = String::new();
+    for (node, group) in &wires.into_iter().group_by(|&(node, _)| node) {
+        writeln!(
+            input,
+            "{}: {}",
+            names[node],
+            group.map(|(_, other)| &names[other]).join(" ")
+        )
+        .unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests!((1, "", 54));
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..10 {
+            let input = generate(20, &mut Rng::new(seed));
+            let graph = parse(&input).unwrap();
+            assert_eq!(graph.len(), 20);
+            // The cut is between the two groups, not around a loosely wired component
+            let (wires, group) = graph.min_cut().unwrap();
+            assert_eq!(wires, 3);
+            assert!((6..=14).contains(&group.len()));
+        }
+    }
+
+    #[test]
+    fn test_part_one_reports_wrong_cut() {
+        assert_eq!(part_one("aaa: bbb\nccc: ddd\n"), None);
+        assert_eq!(
+            crate::template::runner::take_error().as_deref(),
+            Some("the smallest cut is 0 wires, not three")
+        );
+    }
+
+    #[test]
+    fn test_parse_fuzz() {
+        crate::fuzz::check(DAY, |input| parse(input).is_ok());
+    }
+}
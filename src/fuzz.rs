@@ -0,0 +1,287 @@
+/// Fuzzing for the input parsers: mutates the examples at random, looking for input that makes
+/// a parser panic instead of returning an error.
+use std::cell::Cell;
+use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use crate::random::Rng;
+use crate::template::Day;
+
+/// Pieces the mutator splices in, to reach the parsers' separators and limits.
+const TOKENS: [&str; 36] = [
+    "\n",
+    "\n\n",
+    "\r\n",
+    " ",
+    ",",
+    ":",
+    ": ",
+    ";",
+    " -> ",
+    " @ ",
+    "~",
+    "=",
+    "{",
+    "}",
+    "(",
+    ")",
+    "#",
+    ".",
+    "-",
+    "|",
+    "?",
+    "<",
+    ">",
+    "%",
+    "&",
+    "S",
+    "AAA",
+    "ZZZ",
+    "in",
+    "rx",
+    "broadcaster",
+    "x=",
+    "é",
+    "0",
+    "-1",
+    "99999999999999999999999",
+];
+
+/// Numbers to swap in for the ones already there.
+const NUMBERS: [&str; 6] = [
+    "0",
+    "-0",
+    "255",
+    "4294967296",
+    "18446744073709551616",
+    "99999999999999999999999999999999999999999",
+];
+
+/// A few random changes to `input`: changing, dropping and repeating bytes, cutting it short,
+/// swapping lines and splicing in tokens or big numbers.
+pub fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut bytes = input.as_bytes().to_vec();
+    for _ in 0..=rng.index(4) {
+        let at = rng.index(bytes.len() + 1);
+        let end = (at + 1 + rng.index(8)).min(bytes.len());
+        match rng.below(7) {
+            0 if at < end => bytes[at] = rng.below(128) as u8,
+            1 if at < end => {
+                bytes.drain(at..end);
+            }
+            2 if at < end => {
+                let repeated = bytes[at..end].to_vec();
+                bytes.splice(end..end, repeated);
+            }
+            3 => bytes.truncate(at),
+            4 => {
+                let text = String::from_utf8_lossy(&bytes).into_owned();
+                let mut lines: Vec<&str> = text.split('\n').collect();
+                let (a, b) = (rng.index(lines.len()), rng.index(lines.len()));
+                lines.swap(a, b);
+                bytes = lines.join("\n").into_bytes();
+            }
+            5 => {
+                let Some(start) = bytes[at..].iter().position(u8::is_ascii_digit) else {
+                    continue;
+                };
+                let start = at + start;
+                let length = bytes[start..]
+                    .iter()
+                    .position(|b| !b.is_ascii_digit())
+                    .unwrap_or(bytes.len() - start);
+                let number = rng.choose(&NUMBERS);
+                bytes.splice(start..start + length, number.bytes());
+            }
+            _ => {
+                let token = rng.choose(&TOKENS);
+                bytes.splice(at..at, token.bytes());
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/* -------------------------------------------------------------------------- */
+
+thread_local! {
+    /// Whether panics on this thread are expected, and shouldn't be printed.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, catching any panic without printing it.
+fn quietly<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                default(info);
+            }
+        }));
+    });
+
+    QUIET.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.set(false);
+    result.map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panicked".to_string())
+    })
+}
+
+/// An input that made a parser panic, and what it said.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Crash {
+    pub input: String,
+    pub message: String,
+}
+
+/// Cuts down an input that makes `parse` panic, taking out lines and then ever smaller runs
+/// of bytes for as long as it still panics.
+fn minimise(crash: Crash, parse: &impl Fn(&str) -> bool) -> Crash {
+    let mut crash = crash;
+    let still_panics = |input: String, crash: &mut Crash| match quietly(|| parse(&input)) {
+        Err(message) => {
+            *crash = Crash { input, message };
+            true
+        }
+        Ok(_) => false,
+    };
+
+    let mut line = 0;
+    while line < crash.input.lines().count() {
+        let fewer = crash
+            .input
+            .lines()
+            .enumerate()
+            .filter_map(|(i, l)| (i != line).then_some(l.to_string() + "\n"))
+            .collect();
+        if !still_panics(fewer, &mut crash) {
+            line += 1;
+        }
+    }
+
+    let mut chunk = crash.input.len() / 2;
+    while chunk > 0 {
+        let mut at = 0;
+        while at < crash.input.len() {
+            let end = (at + chunk).min(crash.input.len());
+            let shorter = crash
+                .input
+                .get(..at)
+                .zip(crash.input.get(end..))
+                .map(|(before, after)| format!("{before}{after}"));
+            if !shorter.is_some_and(|shorter| still_panics(shorter, &mut crash)) {
+                at += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+    crash
+}
+
+/// Feeds `runs` mutations of `corpus` to `parse`, which says whether the input parsed, and
+/// returns the first input that made it panic, cut down as small as it will go. Mutations that
+/// still parse join the corpus, so later ones get past the first checks more often.
+pub fn find_crash(
+    corpus: &[String],
+    runs: usize,
+    seed: u64,
+    parse: impl Fn(&str) -> bool,
+) -> Option<Crash> {
+    let mut rng = Rng::new(seed);
+    let mut corpus = corpus.to_vec();
+    if corpus.is_empty() {
+        corpus.push(String::new());
+    }
+    for _ in 0..runs {
+        let input = mutate(&corpus[rng.index(corpus.len())], &mut rng);
+        match quietly(|| parse(&input)) {
+            Err(message) => return Some(minimise(Crash { input, message }, &parse)),
+            Ok(false) => {}
+            Ok(true) if corpus.len() < 100 => corpus.push(input),
+            Ok(true) => {
+                let replaced = rng.index(corpus.len());
+                corpus[replaced] = input;
+            }
+        }
+    }
+    None
+}
+
+/// The examples for `day`, such as `data/examples/08.txt` and `08-2.txt`.
+fn examples(day: Day) -> Vec<String> {
+    let Ok(entries) = fs::read_dir("data/examples") else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&day.to_string()))
+        })
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect()
+}
+
+/// Fuzzes `parse` with mutations of `day`'s examples, and panics with the smallest input it
+/// finds that makes the parser panic. Use it like `check(DAY, |input| parse(input).is_ok())`.
+/// Runs 500 mutations from seed 0, unless `FUZZ_RUNS` or `FUZZ_SEED` say otherwise.
+pub fn check(day: Day, parse: impl Fn(&str) -> bool) {
+    let var = |name: &str| env::var(name).ok().and_then(|v| v.parse().ok());
+    let runs = var("FUZZ_RUNS").map_or(500, |runs| runs as usize);
+    let seed = var("FUZZ_SEED").unwrap_or(0);
+    if let Some(crash) = find_crash(&examples(day), runs, seed, parse) {
+        panic!(
+            "day {day} parser panicked (FUZZ_SEED={seed}) on\n{:?}\n{}",
+            crash.input, crash.message
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutate_changes_input() {
+        let mut rng = Rng::new(1);
+        let input = "Time: 7 15 30\nDistance: 9 40 200\n";
+        let mutants: Vec<String> = (0..20).map(|_| mutate(input, &mut rng)).collect();
+        assert!(mutants.iter().filter(|m| *m != input).count() > 15);
+        assert!(mutate("", &mut rng).len() < 50);
+    }
+
+    #[test]
+    fn test_finds_and_minimises_crash() {
+        let parse = |s: &str| {
+            let numbers: Vec<u8> = s
+                .split_whitespace()
+                .map(|n| n.parse().unwrap_or_default())
+                .collect();
+            // Panics on a short line, and on 255 overflowing
+            numbers[1] + 1 > 0
+        };
+        let crash = find_crash(&["12 34 56\n".to_string()], 1000, 0, parse).unwrap();
+        assert!(crash.input.len() <= 4, "{:?}", crash.input);
+    }
+
+    #[test]
+    fn test_no_crash_in_safe_parser() {
+        let parse = |s: &str| s.lines().all(|line| line.len() < 10);
+        assert_eq!(find_crash(&["a\nbc\n".to_string()], 200, 0, parse), None);
+    }
+}
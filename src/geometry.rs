@@ -0,0 +1,282 @@
+/// Directions, turns and points on a grid where rows grow downwards.
+use std::fmt::{self, Display};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+/// One of the four compass directions. North is up, towards lower rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// A change of direction, relative to the current heading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Straight,
+    Right,
+    Back,
+}
+
+impl Direction {
+    /// All directions, clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    #[must_use]
+    pub fn left(self) -> Self {
+        self.turn(Turn::Left)
+    }
+
+    #[must_use]
+    pub fn right(self) -> Self {
+        self.turn(Turn::Right)
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.turn(Turn::Back)
+    }
+
+    #[must_use]
+    pub fn turn(self, turn: Turn) -> Self {
+        let quarters = match turn {
+            Turn::Straight => 0,
+            Turn::Right => 1,
+            Turn::Back => 2,
+            Turn::Left => 3,
+        };
+        Self::ALL[(self as usize + quarters) % 4]
+    }
+
+    /// The turn that changes heading from `self` to `other`.
+    #[must_use]
+    pub fn turn_towards(self, other: Self) -> Turn {
+        match (other as usize + 4 - self as usize) % 4 {
+            0 => Turn::Straight,
+            1 => Turn::Right,
+            2 => Turn::Back,
+            _ => Turn::Left,
+        }
+    }
+
+    /// The `(row, column)` offset of a single step.
+    #[must_use]
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::East => (0, 1),
+            Direction::South => (1, 0),
+            Direction::West => (0, -1),
+        }
+    }
+
+    #[must_use]
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::North | Direction::South)
+    }
+}
+
+/// Returned when a byte is not one of the known direction encodings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseDirectionError(pub u8);
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a direction", self.0 as char)
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
+impl TryFrom<u8> for Direction {
+    type Error = ParseDirectionError;
+
+    /// Parses the encodings used by the puzzles: `NESW`, `UDLR`, arrows like `^>v<`,
+    /// and the digits `0-3` for right, down, left and up.
+    fn try_from(b: u8) -> Result<Self, Self::Error> {
+        match b {
+            b'N' | b'U' | b'^' | b'3' => Ok(Direction::North),
+            b'E' | b'R' | b'>' | b'0' => Ok(Direction::East),
+            b'S' | b'D' | b'v' | b'1' => Ok(Direction::South),
+            b'W' | b'L' | b'<' | b'2' => Ok(Direction::West),
+            _ => Err(ParseDirectionError(b)),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            [b] => Direction::try_from(*b),
+            _ => Err(ParseDirectionError(s.bytes().next().unwrap_or(b' '))),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A position on a grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub row: T,
+    pub column: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(row: T, column: T) -> Self {
+        Self { row, column }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// The number of orthogonal steps between two points.
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        let distance = |a: T, b: T| if a > b { a - b } else { b - a };
+        distance(self.row, other.row) + distance(self.column, other.column)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.row + other.row, self.column + other.column)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.row - other.row, self.column - other.column)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((row, column): (T, T)) -> Self {
+        Self::new(row, column)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.row, point.column)
+    }
+}
+
+macro_rules! signed_point {
+    ($($t:ty),*) => {$(
+        impl Point<$t> {
+            #[must_use]
+            pub fn step(self, direction: Direction) -> Self {
+                self.step_n(direction, 1)
+            }
+
+            #[must_use]
+            pub fn step_n(self, direction: Direction, n: $t) -> Self {
+                let (rows, columns) = direction.delta();
+                Self::new(self.row + rows as $t * n, self.column + columns as $t * n)
+            }
+
+            /// Wraps the point into `0..height` and `0..width`, as if the plane was tiled.
+            #[must_use]
+            pub fn wrapping(self, height: $t, width: $t) -> Self {
+                Self::new(self.row.rem_euclid(height), self.column.rem_euclid(width))
+            }
+        }
+    )*};
+}
+
+signed_point!(i32, i64, isize);
+
+impl Point<usize> {
+    /// Takes a step, or `None` if that would go below row or column zero.
+    #[must_use]
+    pub fn checked_step(self, direction: Direction) -> Option<Self> {
+        let (rows, columns) = direction.delta();
+        Some(Self::new(
+            self.row.checked_add_signed(rows)?,
+            self.column.checked_add_signed(columns)?,
+        ))
+    }
+
+    /// Takes a step, or `None` if that would leave `0..height` and `0..width`.
+    #[must_use]
+    pub fn step_within(self, direction: Direction, height: usize, width: usize) -> Option<Self> {
+        self.checked_step(direction)
+            .filter(|next| next.row < height && next.column < width)
+    }
+
+    /// Takes a step, wrapping around to the other side of `0..height` and `0..width`.
+    #[must_use]
+    pub fn wrapping_step(self, direction: Direction, height: usize, width: usize) -> Self {
+        let (rows, columns) = direction.delta();
+        Self::new(
+            (self.row + height).wrapping_add_signed(rows) % height,
+            (self.column + width).wrapping_add_signed(columns) % width,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        use Direction::*;
+        assert_eq!(North.left(), West);
+        assert_eq!(West.right(), North);
+        assert_eq!(East.reverse(), West);
+        for from in Direction::ALL {
+            for to in Direction::ALL {
+                assert_eq!(from.turn(from.turn_towards(to)), to);
+            }
+        }
+        assert_eq!(South.turn_towards(West), Turn::Right);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Direction::try_from(b'U'), Ok(Direction::North));
+        assert_eq!(Direction::try_from(b'0'), Ok(Direction::East));
+        assert_eq!(Direction::try_from(b'v'), Ok(Direction::South));
+        assert_eq!("L".parse(), Ok(Direction::West));
+        assert_eq!("X".parse::<Direction>(), Err(ParseDirectionError(b'X')));
+        assert_eq!("RR".parse::<Direction>(), Err(ParseDirectionError(b'R')));
+    }
+
+    #[test]
+    fn test_signed_steps() {
+        let point = Point::new(0i64, 0);
+        assert_eq!(point.step(Direction::North), Point::new(-1, 0));
+        assert_eq!(point.step_n(Direction::East, 5), Point::new(0, 5));
+        assert_eq!(Point::new(-1i32, 12).wrapping(10, 10), Point::new(9, 2));
+        assert_eq!(Point::new(-1, 3).manhattan(Point::new(2, -1)), 7);
+    }
+
+    #[test]
+    fn test_unsigned_steps() {
+        let point = Point::new(0usize, 2);
+        assert_eq!(point.checked_step(Direction::North), None);
+        assert_eq!(point.checked_step(Direction::West), Some(Point::new(0, 1)));
+        assert_eq!(point.step_within(Direction::East, 3, 3), None);
+        assert_eq!(
+            point.wrapping_step(Direction::North, 3, 3),
+            Point::new(2, 2)
+        );
+        assert_eq!(point.wrapping_step(Direction::East, 3, 3), Point::new(0, 0));
+        assert_eq!(point.manhattan(Point::new(2, 0)), 4);
+    }
+}
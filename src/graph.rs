@@ -0,0 +1,373 @@
+/// Graphs with labelled nodes and weighted edges, and some searches over them.
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::fmt::{Debug, Write};
+use std::hash::Hash;
+
+/// Refers to a node by its position in the graph, in the order nodes were first seen.
+pub type NodeId = usize;
+
+/// A directed graph with weighted edges. Nodes are labelled with an `N`, and interned so
+/// searches can work with dense [`NodeId`]s. Undirected graphs have each edge both ways.
+#[derive(Clone, Debug)]
+pub struct Graph<N> {
+    labels: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    edges: Vec<BTreeMap<NodeId, usize>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Self {
+            labels: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of the node labelled `label`, adding it if it's new.
+    pub fn intern(&mut self, label: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+        let id = self.labels.len();
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+        self.edges.push(BTreeMap::new());
+        id
+    }
+
+    #[must_use]
+    pub fn id(&self, label: &N) -> Option<NodeId> {
+        self.ids.get(label).copied()
+    }
+
+    #[must_use]
+    pub fn label(&self, id: NodeId) -> &N {
+        &self.labels[id]
+    }
+
+    /// The number of nodes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Adds an edge from `from` to `to`, replacing any edge already between them.
+    pub fn add_edge(&mut self, from: N, to: N, weight: usize) {
+        let (from, to) = (self.intern(from), self.intern(to));
+        self.edges[from].insert(to, weight);
+    }
+
+    /// Adds an edge each way between `a` and `b`.
+    pub fn add_undirected_edge(&mut self, a: N, b: N, weight: usize) {
+        self.add_edge(a.clone(), b.clone(), weight);
+        self.add_edge(b, a, weight);
+    }
+
+    /// The nodes reachable from `id` over a single edge, with the weight of that edge.
+    pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = (NodeId, usize)> + '_ {
+        self.edges[id].iter().map(|(&to, &weight)| (to, weight))
+    }
+
+    /// Every edge as `(from, to, weight)`.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId, usize)> + '_ {
+        (0..self.len()).flat_map(move |from| {
+            self.neighbours(from)
+                .map(move |(to, weight)| (from, to, weight))
+        })
+    }
+
+    /// Removes every node with exactly two neighbours, joining up the edges running through
+    /// it, so corridors in a maze become a single edge as long as the whole corridor. Meant
+    /// for undirected graphs; where that joins nodes already linked, the heavier edge is kept.
+    #[must_use]
+    pub fn contract_degree_two(&self) -> Self {
+        let mut edges = self.edges.clone();
+        let mut removed = vec![false; self.len()];
+
+        for current in 0..self.len() {
+            if edges[current].len() != 2 {
+                continue;
+            }
+            let through = std::mem::take(&mut edges[current]);
+            removed[current] = true;
+            let [(left, left_weight), (right, right_weight)]: [(NodeId, usize); 2] =
+                through.into_iter().collect::<Vec<_>>().try_into().unwrap();
+
+            for (from, to, onward) in [(left, right, right_weight), (right, left, left_weight)] {
+                if let Some(weight) = edges[from].remove(&current) {
+                    let joined = edges[from].entry(to).or_default();
+                    *joined = (*joined).max(weight + onward);
+                }
+            }
+        }
+
+        let mut graph = Self::new();
+        for id in (0..self.len()).filter(|&id| !removed[id]) {
+            graph.intern(self.labels[id].clone());
+        }
+        for (from, to_weights) in edges.iter().enumerate() {
+            for (&to, &weight) in to_weights {
+                graph.add_edge(self.labels[from].clone(), self.labels[to].clone(), weight);
+            }
+        }
+        graph
+    }
+
+    /// Groups the nodes into connected components, ignoring which way the edges go.
+    #[must_use]
+    pub fn components(&self) -> Vec<Vec<NodeId>> {
+        let mut undirected = vec![Vec::new(); self.len()];
+        for (from, to, _) in self.edges() {
+            undirected[from].push(to);
+            undirected[to].push(from);
+        }
+
+        let mut seen = vec![false; self.len()];
+        let mut components = Vec::new();
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![start];
+            let mut i = 0;
+            while let Some(&current) = component.get(i) {
+                for &next in &undirected[current] {
+                    if !seen[next] {
+                        seen[next] = true;
+                        component.push(next);
+                    }
+                }
+                i += 1;
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// The length of the longest path from `start` to `goal` that visits no node twice, or
+    /// `None` if `goal` can't be reached. This tries every path, so contract the graph first.
+    #[must_use]
+    pub fn longest_path(&self, start: NodeId, goal: NodeId) -> Option<usize> {
+        fn walk<N>(
+            graph: &Graph<N>,
+            current: NodeId,
+            goal: NodeId,
+            visited: &mut [bool],
+        ) -> Option<usize> {
+            if current == goal {
+                return Some(0);
+            }
+
+            visited[current] = true;
+            let longest = graph.edges[current]
+                .iter()
+                .filter_map(|(&next, &weight)| {
+                    if visited[next] {
+                        return None;
+                    }
+                    Some(weight + walk(graph, next, goal, visited)?)
+                })
+                .max();
+            visited[current] = false;
+
+            longest
+        }
+
+        walk(self, start, goal, &mut vec![false; self.len()])
+    }
+
+    /// The cheapest way to split an undirected graph in two, as the total weight of the
+    /// edges cut and the nodes on one side. `None` if there are fewer than two nodes.
+    ///
+    /// <https://en.wikipedia.org/wiki/Stoer%E2%80%93Wagner_algorithm>
+    #[must_use]
+    pub fn min_cut(&self) -> Option<(usize, Vec<NodeId>)> {
+        if self.len() < 2 {
+            return None;
+        }
+        if let [first, _, ..] = self.components().as_slice() {
+            return Some((0, first.clone()));
+        }
+
+        let mut adjacency: Vec<HashMap<NodeId, usize>> = self
+            .edges
+            .iter()
+            .map(|edges| edges.iter().map(|(&to, &weight)| (to, weight)).collect())
+            .collect();
+        let mut members: Vec<Vec<NodeId>> = (0..self.len()).map(|id| vec![id]).collect();
+        let mut active: Vec<NodeId> = (0..self.len()).collect();
+        let mut best: Option<(usize, Vec<NodeId>)> = None;
+
+        while active.len() > 1 {
+            // Add nodes most tightly connected to those already added, until all are
+            let mut weights = vec![0; self.len()];
+            let mut added = vec![false; self.len()];
+            let mut queue = BinaryHeap::from([(0, active[0])]);
+            let (mut previous, mut last, mut cut) = (active[0], active[0], 0);
+            while let Some((weight, current)) = queue.pop() {
+                if added[current] || weight != weights[current] {
+                    continue;
+                }
+                added[current] = true;
+                (previous, last, cut) = (last, current, weight);
+                for (&next, &weight) in &adjacency[current] {
+                    if !added[next] {
+                        weights[next] += weight;
+                        queue.push((weights[next], next));
+                    }
+                }
+            }
+
+            // Cutting off the last node added is the best cut separating it from the
+            // one before, so note it and then merge the two
+            if best.as_ref().is_none_or(|&(best, _)| cut < best) {
+                best = Some((cut, members[last].clone()));
+            }
+            for (next, weight) in std::mem::take(&mut adjacency[last]) {
+                adjacency[next].remove(&last);
+                if next != previous {
+                    *adjacency[previous].entry(next).or_default() += weight;
+                    *adjacency[next].entry(previous).or_default() += weight;
+                }
+            }
+            let merged = std::mem::take(&mut members[last]);
+            members[previous].extend(merged);
+            active.retain(|&id| id != last);
+        }
+
+        best
+    }
+}
+
+impl<N: Debug> Graph<N> {
+    /// Describes the graph in Graphviz's DOT language, for viewing with `dot -Tsvg`.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let quoted = |id: NodeId| format!("{:?}", format!("{:?}", self.labels[id]));
+        let mut dot = String::from("digraph {\n");
+        for id in 0..self.labels.len() {
+            writeln!(dot, "    {id} [label={}];", quoted(id)).unwrap();
+        }
+        for (from, to_weights) in self.edges.iter().enumerate() {
+            for (to, weight) in to_weights {
+                writeln!(dot, "    {from} -> {to} [label={weight}];").unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two squares, joined by a single edge between b and e.
+    fn squares() -> Graph<char> {
+        let mut graph = Graph::new();
+        for (a, b) in [
+            ('a', 'b'),
+            ('b', 'c'),
+            ('c', 'd'),
+            ('d', 'a'),
+            ('a', 'c'),
+            ('e', 'f'),
+            ('f', 'g'),
+            ('g', 'h'),
+            ('h', 'e'),
+            ('b', 'e'),
+        ] {
+            graph.add_undirected_edge(a, b, 1);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_intern() {
+        let graph = squares();
+        assert_eq!(graph.len(), 8);
+        assert_eq!(graph.id(&'c'), Some(2));
+        assert_eq!(*graph.label(4), 'e');
+        assert_eq!(graph.id(&'z'), None);
+        assert_eq!(
+            graph.neighbours(1).collect::<Vec<_>>(),
+            [(0, 1), (2, 1), (4, 1)]
+        );
+        assert_eq!(graph.edges().count(), 20);
+    }
+
+    #[test]
+    fn test_contract_degree_two() {
+        let mut graph = Graph::new();
+        for (a, b, weight) in [('a', 'b', 1), ('b', 'c', 2), ('c', 'd', 3), ('c', 'e', 1)] {
+            graph.add_undirected_edge(a, b, weight);
+        }
+        let contracted = graph.contract_degree_two();
+        assert_eq!(contracted.len(), 4);
+        let a = contracted.id(&'a').unwrap();
+        let c = contracted.id(&'c').unwrap();
+        assert_eq!(contracted.neighbours(a).collect::<Vec<_>>(), [(c, 3)]);
+        assert_eq!(
+            contracted.longest_path(a, contracted.id(&'d').unwrap()),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn test_components() {
+        let mut graph = squares();
+        graph.add_edge('x', 'y', 1);
+        graph.intern('z');
+        let components = graph.components();
+        assert_eq!(
+            components.iter().map(Vec::len).collect::<Vec<_>>(),
+            [8, 2, 1]
+        );
+        assert_eq!(graph.min_cut(), Some((0, components[0].clone())));
+    }
+
+    #[test]
+    fn test_longest_path() {
+        let graph = squares();
+        let (a, h) = (graph.id(&'a').unwrap(), graph.id(&'h').unwrap());
+        // a -> d -> c -> b -> e -> f -> g -> h
+        assert_eq!(graph.longest_path(a, h), Some(7));
+        let mut graph = graph;
+        let z = graph.intern('z');
+        assert_eq!(graph.longest_path(a, z), None);
+    }
+
+    #[test]
+    fn test_min_cut() {
+        let graph = squares();
+        let (weight, mut side) = graph.min_cut().unwrap();
+        assert_eq!(weight, 1);
+        side.sort_unstable();
+        assert!(side == [0, 1, 2, 3] || side == [4, 5, 6, 7], "{side:?}");
+        assert_eq!(Graph::<char>::new().min_cut(), None);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut graph = Graph::new();
+        graph.add_edge("a\"", "b", 2);
+        assert_eq!(
+            graph.to_dot(),
+            "digraph {\n    0 [label=\"\\\"a\\\\\\\"\\\"\"];\n    1 [label=\"\\\"b\\\"\"];\n    0 -> 1 [label=2];\n}\n"
+        );
+    }
+}
@@ -0,0 +1,444 @@
+/// A rectangular grid of cells, as found in many of the puzzle inputs.
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction, Point};
+use crate::parse::{ParseError, Span};
+
+/// A position in a grid as `(row, column)`.
+pub type Pos = (usize, usize);
+
+/// Offsets of the four orthogonal neighbours, in reading order.
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets of all eight neighbours, in reading order.
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A grid of `width * height` cells stored in row-major order.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    #[must_use]
+    pub fn new(width: usize, height: usize, data: Vec<T>) -> Self {
+        assert_eq!(
+            data.len(),
+            width * height,
+            "grid should have width * height cells"
+        );
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Parses a grid from lines of text, mapping every byte to a cell.
+    pub fn parse(s: &str, mut f: impl FnMut(u8) -> T) -> Self {
+        let mut data = Vec::with_capacity(s.len());
+        let mut width = 0;
+        let mut height = 0;
+
+        for line in s.trim_end_matches('\n').lines() {
+            if height == 0 {
+                width = line.len();
+            }
+            assert_eq!(
+                line.len(),
+                width,
+                "line {} should be {width} wide",
+                height + 1
+            );
+            data.extend(line.bytes().map(&mut f));
+            height += 1;
+        }
+
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// Like [`Grid::parse`], but with an error instead of a panic for ragged lines, or for
+    /// bytes that `f` has no cell for.
+    pub fn try_parse(s: &str, f: impl FnMut(u8) -> Option<T>) -> Result<Self, ParseError> {
+        Self::from_span(Span::new(s), f)
+    }
+
+    /// Like [`Grid::try_parse`], for a grid that's only part of the input, so that errors
+    /// point at the right line.
+    pub fn from_span(span: Span, mut f: impl FnMut(u8) -> Option<T>) -> Result<Self, ParseError> {
+        let mut lines: Vec<Span> = span.lines().collect();
+        while lines.last().is_some_and(|line| line.as_str().is_empty()) {
+            lines.pop();
+        }
+
+        let mut data = Vec::with_capacity(span.as_str().len());
+        let mut width = 0;
+        let mut height = 0;
+        for line in lines {
+            let text = line.as_str();
+            if height == 0 {
+                width = text.len();
+            } else if text.len() != width {
+                return Err(line.error(format!(
+                    "expected a line {width} wide, found {}",
+                    text.len()
+                )));
+            }
+            for (i, b) in text.bytes().enumerate() {
+                let cell = f(b).ok_or_else(|| {
+                    // Point at the whole character the byte is part of
+                    let start = (0..=i).rev().find(|&j| text.is_char_boundary(j)).unwrap();
+                    let shown = text[start..].chars().next().unwrap_or_default();
+                    line.skip(start).error(format!("unexpected {shown:?}"))
+                })?;
+                data.push(cell);
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Is `pos` inside the grid?
+    #[must_use]
+    pub fn contains(&self, (row, column): Pos) -> bool {
+        row < self.height && column < self.width
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.data[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.data[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// Converts signed coordinates to a position, or `None` if they are outside the grid.
+    #[must_use]
+    pub fn checked(&self, row: isize, column: isize) -> Option<Pos> {
+        let pos = (usize::try_from(row).ok()?, usize::try_from(column).ok()?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Access with signed coordinates, returning `None` outside the grid.
+    #[must_use]
+    pub fn get_checked(&self, row: isize, column: isize) -> Option<&T> {
+        self.checked(row, column).map(|pos| &self[pos])
+    }
+
+    /// Access with signed coordinates, as if the grid was repeated infinitely in all directions.
+    #[must_use]
+    pub fn get_wrapping(&self, row: isize, column: isize) -> &T {
+        #[allow(clippy::cast_possible_wrap)]
+        let (height, width) = (self.height as isize, self.width as isize);
+        #[allow(clippy::cast_sign_loss)]
+        let pos = (
+            row.rem_euclid(height) as usize,
+            column.rem_euclid(width) as usize,
+        );
+        &self[pos]
+    }
+
+    /// Moves `pos` by `(rows, columns)`, or `None` if that leaves the grid.
+    #[must_use]
+    pub fn offset(&self, pos: Pos, (rows, columns): (isize, isize)) -> Option<Pos> {
+        let row = pos.0.checked_add_signed(rows)?;
+        let column = pos.1.checked_add_signed(columns)?;
+        self.contains((row, column)).then_some((row, column))
+    }
+
+    /// Takes a step from `pos` in `direction`, or `None` if that leaves the grid.
+    #[must_use]
+    pub fn step(&self, pos: Pos, direction: Direction) -> Option<Pos> {
+        self.offset(pos, direction.delta())
+    }
+
+    /// The orthogonal neighbours of `pos` inside the grid, in reading order.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` inside the grid, in reading order.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        SURROUNDING
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.data[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "column {column} out of bounds");
+        self.data.iter().skip(column).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /// All cells with their positions, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| ((i / self.width, i % self.width), cell))
+    }
+
+    /// The position of the first cell matching `predicate`, in reading order.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    #[must_use]
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Mirrors the grid along its main diagonal, so rows become columns.
+    #[must_use]
+    pub fn transposed(&self) -> Self {
+        self.rebuild(self.height, self.width, |(row, column)| (column, row))
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    #[must_use]
+    pub fn rotated_clockwise(&self) -> Self {
+        self.rebuild(self.height, self.width, |(row, column)| {
+            (self.height - 1 - column, row)
+        })
+    }
+
+    /// Rotates the grid a quarter turn anticlockwise.
+    #[must_use]
+    pub fn rotated_anticlockwise(&self) -> Self {
+        self.rebuild(self.height, self.width, |(row, column)| {
+            (column, self.width - 1 - row)
+        })
+    }
+
+    /// Builds a new grid by looking up where each of its cells comes from in this grid.
+    fn rebuild(&self, width: usize, height: usize, source: impl Fn(Pos) -> Pos) -> Self {
+        let data = (0..height)
+            .flat_map(|row| (0..width).map(move |column| (row, column)))
+            .map(|pos| self[source(pos)].clone())
+            .collect();
+        Self::new(width, height, data)
+    }
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self {
+            data: Vec::new(),
+            width: 0,
+            height: 0,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &Self::Output {
+        &self[(point.row, point.column)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut Self::Output {
+        &mut self[(point.row, point.column)]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<char> {
+        Grid::parse("abc\ndef\n", char::from)
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 should be 3 wide")]
+    fn test_parse_ragged() {
+        Grid::parse("abc\nde\n", char::from);
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let digits = |b: u8| b.is_ascii_digit().then_some(b - b'0');
+        let grid = Grid::try_parse("12\n34\n", digits).unwrap();
+        assert_eq!((grid.width(), grid.height(), grid[(1, 0)]), (2, 2, 3));
+
+        let error = Grid::try_parse("12\n3\n", digits).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected a line 2 wide, found 1"
+        );
+        let error = Grid::try_parse("123\n3é\n", digits).unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 2: unexpected 'é'");
+    }
+
+    #[test]
+    fn test_access() {
+        let grid = example();
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get_checked(-1, 0), None);
+        assert_eq!(grid.get_checked(1, 2), Some(&'f'));
+        assert_eq!(grid.get_wrapping(-1, -1), &'f');
+        assert_eq!(grid.get_wrapping(4, 7), &'b');
+        assert_eq!(grid[Point::new(0, 2)], 'c');
+        assert_eq!(grid.step((0, 2), Direction::East), None);
+        assert_eq!(grid.step((0, 2), Direction::South), Some((1, 2)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = example();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0), (1, 2)]
+        );
+        assert_eq!(
+            grid.neighbours8((0, 1)).collect::<Vec<_>>(),
+            [(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_columns_and_transpose() {
+        let grid = example();
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.transposed().to_string(), "ad\nbe\ncf\n");
+    }
+
+    #[test]
+    fn test_rotate() {
+        let grid = example();
+        assert_eq!(grid.rotated_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotated_anticlockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(
+            grid.rotated_clockwise()
+                .rotated_clockwise()
+                .rotated_anticlockwise()
+                .rotated_anticlockwise(),
+            grid
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = example();
+        assert_eq!(grid.find(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.find(|&c| c == 'z'), None);
+        assert_eq!(
+            grid.iter().filter(|(_, &c)| c > 'c').count(),
+            grid.map(|&c| c > 'c').iter().filter(|(_, &b)| b).count()
+        );
+    }
+}
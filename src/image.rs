@@ -0,0 +1,462 @@
+/// Pictures of puzzle state, saved as PPM, PNG or animated GIF without any image crates.
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::grid::{Grid, Pos};
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// A picture made of `width * height` pixels, addressed by `(row, column)` like a [`Grid`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pixels: Grid<Rgb>,
+}
+
+impl Image {
+    /// An image filled with `background`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            pixels: Grid::filled(width, height, background),
+        }
+    }
+
+    /// An image with a `scale` by `scale` square of colour for every cell of `grid`.
+    pub fn from_grid<T>(grid: &Grid<T>, scale: usize, mut colour: impl FnMut(&T) -> Rgb) -> Self {
+        let mut image = Self::new(grid.width() * scale, grid.height() * scale, BLACK);
+        for ((row, column), value) in grid.iter() {
+            image.fill((row * scale, column * scale), (scale, scale), colour(value));
+        }
+        image
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.pixels.width()
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.pixels.height()
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Pos) -> Option<Rgb> {
+        self.pixels.get(pos).copied()
+    }
+
+    /// Colours the pixel at `pos`. Anything outside the image is left off.
+    pub fn set(&mut self, pos: Pos, colour: Rgb) {
+        if let Some(pixel) = self.pixels.get_mut(pos) {
+            *pixel = colour;
+        }
+    }
+
+    /// Colours a rectangle `(height, width)` pixels in size, with `top_left` as its corner.
+    pub fn fill(&mut self, top_left: Pos, (height, width): (usize, usize), colour: Rgb) {
+        for row in top_left.0..top_left.0 + height {
+            for column in top_left.1..top_left.1 + width {
+                self.set((row, column), colour);
+            }
+        }
+    }
+
+    /// Every pixel becomes a `factor` by `factor` square.
+    #[must_use]
+    pub fn scaled(&self, factor: usize) -> Self {
+        Self::from_grid(&self.pixels, factor, |&colour| colour)
+    }
+
+    /// The image as a binary PPM, which most image viewers can open.
+    #[must_use]
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width(), self.height()).into_bytes();
+        for (_, pixel) in self.pixels.iter() {
+            out.extend(pixel);
+        }
+        out
+    }
+
+    /// The image as a PNG. The pixels are stored uncompressed, which keeps the encoder
+    /// small at the cost of a bigger file.
+    ///
+    /// <https://www.w3.org/TR/png/>
+    #[must_use]
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width() * 3 + 1) * self.height());
+        for row in self.pixels.rows() {
+            // Each row starts with its filter type, which is none
+            raw.push(0);
+            raw.extend(row.iter().flatten());
+        }
+
+        let mut header = Vec::new();
+        header.extend((self.width() as u32).to_be_bytes());
+        header.extend((self.height() as u32).to_be_bytes());
+        // 8 bits per channel, RGB, and the only compression, filter and interlace methods
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut out, b"IHDR", &header);
+        png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+}
+
+/// A colour on a scale from black through red and yellow to white, for `amount` between 0
+/// and 1. There are 64 steps, which leaves room in a GIF's palette for other colours.
+#[must_use]
+pub fn heat(amount: f64) -> Rgb {
+    let step = (amount.clamp(0.0, 1.0) * 63.0).round() as u32;
+    // Each third of the scale brings up one more channel
+    let channel = |third: u32| (step * 3).saturating_sub(third * 63).min(63) * 255 / 63;
+    [channel(0) as u8, channel(1) as u8, channel(2) as u8]
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Wraps `data` in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let length = block.len() as u16;
+        out.extend(length.to_le_bytes());
+        out.extend((!length).to_le_bytes());
+        out.extend(block);
+    }
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend(((b << 16) | a).to_be_bytes());
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// The frames as an animated GIF that loops forever, showing each for `delay`. All the
+/// frames must be the same size, with no more than 256 colours between them.
+///
+/// <https://www.w3.org/Graphics/GIF/spec-gif89a.txt>
+pub fn to_gif(frames: &[Image], delay: Duration) -> io::Result<Vec<u8>> {
+    let first = frames.first().ok_or_else(|| invalid("no frames to save"))?;
+    let (width, height) = (first.width(), first.height());
+    if frames
+        .iter()
+        .any(|frame| (frame.width(), frame.height()) != (width, height))
+    {
+        return Err(invalid("frames should all be the same size"));
+    }
+    let size = |n: usize| u16::try_from(n).map_err(|_| invalid("image too big for a GIF"));
+    let (width, height) = (size(width)?, size(height)?);
+
+    let mut palette: Vec<Rgb> = Vec::new();
+    let mut indexes: HashMap<Rgb, u8> = HashMap::new();
+    for frame in frames {
+        for (_, &colour) in frame.pixels.iter() {
+            if let Entry::Vacant(entry) = indexes.entry(colour) {
+                let index = u8::try_from(palette.len())
+                    .map_err(|_| invalid("a GIF can have at most 256 colours"))?;
+                entry.insert(index);
+                palette.push(colour);
+            }
+        }
+    }
+    // The colour table has a power of two entries, at least 2
+    let bits = (palette.len().max(2) - 1).ilog2() as u8 + 1;
+    palette.resize(1 << bits, BLACK);
+
+    let mut out = b"GIF89a".to_vec();
+    out.extend(width.to_le_bytes());
+    out.extend(height.to_le_bytes());
+    out.extend([0xf0 | (bits - 1), 0, 0]);
+    out.extend(palette.iter().flatten());
+    // Loop forever
+    out.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    let centiseconds = u16::try_from(delay.as_millis() / 10).unwrap_or(u16::MAX);
+    for frame in frames {
+        out.extend([0x21, 0xf9, 4, 0]);
+        out.extend(centiseconds.to_le_bytes());
+        out.extend([0, 0]);
+
+        out.push(0x2c);
+        out.extend([0, 0, 0, 0]);
+        out.extend(width.to_le_bytes());
+        out.extend(height.to_le_bytes());
+        out.push(0);
+
+        let min_code_size = bits.max(2);
+        out.push(min_code_size);
+        let indexes: Vec<u8> = frame.pixels.iter().map(|(_, c)| indexes[c]).collect();
+        for block in lzw(&indexes, min_code_size).chunks(255) {
+            out.push(block.len() as u8);
+            out.extend(block);
+        }
+        out.push(0);
+    }
+    out.push(0x3b);
+    Ok(out)
+}
+
+/// Packs codes of varying widths into bytes, least significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += width;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Compresses colour indexes with GIF's flavour of LZW.
+fn lzw(indexes: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear: u16 = 1 << min_code_size;
+    let end = clear + 1;
+    let mut writer = BitWriter::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut width = min_code_size + 1;
+    let mut next = end + 1;
+    writer.write(clear, width);
+
+    let Some((&first, rest)) = indexes.split_first() else {
+        writer.write(end, width);
+        return writer.finish();
+    };
+    let mut prefix = u16::from(first);
+    for &index in rest {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        writer.write(prefix, width);
+        table.insert((prefix, index), next);
+        // The decoder is a code behind, so it widens once it has seen the code after this
+        if next == 1 << width && width < 12 {
+            width += 1;
+        }
+        next += 1;
+        if next == 4096 {
+            writer.write(clear, width);
+            table.clear();
+            width = min_code_size + 1;
+            next = end + 1;
+        }
+        prefix = u16::from(index);
+    }
+    writer.write(prefix, width);
+    // The decoder adds an entry for that last code, and may widen before reading the end
+    if next == 1 << width && width < 12 {
+        width += 1;
+    }
+    writer.write(end, width);
+    writer.finish()
+}
+
+/// Writes `frames` to `path`, picking the format from its extension. A `.gif` is animated,
+/// showing each frame for `delay`, while a `.png` or `.ppm` gets the last frame.
+pub fn save(frames: &[Image], path: &Path, delay: Duration) -> io::Result<()> {
+    let last = frames.last().ok_or_else(|| invalid("no frames to save"))?;
+    let bytes = match path.extension().and_then(|e| e.to_str()) {
+        Some("gif") => to_gif(frames, delay)?,
+        Some("png") => last.to_png(),
+        Some("ppm") => last.to_ppm(),
+        _ => return Err(invalid("can only save .png, .ppm and .gif images")),
+    };
+    fs::write(path, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        let grid = Grid::parse("#.\n.#", |b| b == b'#');
+        Image::from_grid(&grid, 2, |&on| if on { WHITE } else { [255, 0, 0] })
+    }
+
+    #[test]
+    fn test_heat() {
+        assert_eq!(heat(0.0), BLACK);
+        assert_eq!(heat(1.0), WHITE);
+        assert_eq!(heat(1.0 / 3.0), [255, 0, 0]);
+        assert_eq!(heat(2.0 / 3.0), [255, 255, 0]);
+        assert_eq!(heat(-1.0), BLACK);
+        let colours: std::collections::HashSet<Rgb> =
+            (0..=1000).map(|i| heat(f64::from(i) / 1000.0)).collect();
+        assert_eq!(colours.len(), 64);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = checkerboard();
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.get((1, 1)), Some(WHITE));
+        assert_eq!(image.get((1, 2)), Some([255, 0, 0]));
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n\xff\xff\xff"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+    }
+
+    #[test]
+    fn test_png() {
+        let png = checkerboard().to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"));
+        // Known values from the spec's CRC: the whole of an empty IEND chunk
+        assert!(png.ends_with(b"\x00\x00\x00\x00IEND\xae\x42\x60\x82"));
+
+        // The image data is the rows, each after a filter byte, in one stored block
+        let data = &png[8 + 25 + 8..png.len() - 12 - 4];
+        assert_eq!(&data[..7], [0x78, 0x01, 1, 52, 0, !52, !0]);
+        let rows: Vec<&[u8]> = data[7..data.len() - 4].chunks(13).collect();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0][..7], [0, 255, 255, 255, 255, 255, 255]);
+        assert_eq!(rows[3][..7], [0, 255, 0, 0, 255, 0, 0]);
+    }
+
+    /// Decodes GIF LZW the way a viewer would.
+    fn unlzw(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut width = 0;
+        let mut previous: Option<usize> = None;
+        let mut out = Vec::new();
+        let (mut buffer, mut bits, mut bytes) = (0u32, 0, bytes.iter());
+        loop {
+            while bits < width.max(min_code_size + 1) {
+                buffer |= u32::from(*bytes.next().unwrap()) << bits;
+                bits += 8;
+            }
+            let width_now = width.max(min_code_size + 1);
+            let code = (buffer & ((1 << width_now) - 1)) as usize;
+            buffer >>= width_now;
+            bits -= width_now;
+
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.extend([vec![], vec![]]);
+                width = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return out;
+            }
+            let entry = match previous {
+                None => table[code].clone(),
+                Some(previous) => {
+                    let entry = table.get(code).cloned().unwrap_or_else(|| {
+                        let mut entry = table[previous].clone();
+                        entry.push(table[previous][0]);
+                        entry
+                    });
+                    let mut added = table[previous].clone();
+                    added.push(entry[0]);
+                    table.push(added);
+                    if table.len() == 1 << width && width < 12 {
+                        width += 1;
+                    }
+                    entry
+                }
+            };
+            out.extend(&entry);
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn test_lzw() {
+        let short = [0, 0, 1, 1, 0, 1, 0, 1, 0, 1, 1, 1];
+        assert_eq!(unlzw(&lzw(&short, 2), 2), short);
+        // Long enough to widen the codes all the way and clear the table
+        let mut state = 1u32;
+        let long: Vec<u8> = (0..50_000)
+            .map(|i| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                if i % 7 == 0 {
+                    (state >> 16) as u8
+                } else {
+                    (state >> 29) as u8
+                }
+            })
+            .collect();
+        assert_eq!(unlzw(&lzw(&long, 8), 8), long);
+        assert_eq!(unlzw(&lzw(&[], 2), 2), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_gif() {
+        let frames = [checkerboard(), checkerboard().scaled(1)];
+        let gif = to_gif(&frames, Duration::from_millis(250)).unwrap();
+        assert!(gif.starts_with(b"GIF89a\x04\x00\x04\x00\xf0"));
+        assert!(gif.ends_with(b"\x00\x3b"));
+        // The graphic control block for each frame, with the delay in hundredths
+        let delays = gif.windows(6).filter(|w| *w == b"\x21\xf9\x04\x00\x19\x00");
+        assert_eq!(delays.count(), 2);
+
+        let too_many = Image::from_grid(&Grid::new(257, 1, (0..257).collect()), 1, |&i: &u32| {
+            [i as u8, (i >> 8) as u8, 0]
+        });
+        assert!(to_gif(&[too_many], Duration::ZERO).is_err());
+        assert!(to_gif(&[checkerboard(), Image::new(1, 1, BLACK)], Duration::ZERO).is_err());
+    }
+}
@@ -1,3 +1,18 @@
+pub mod cycles;
+pub mod days;
+pub mod fuzz;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod image;
+pub mod numeric;
+pub mod parse;
+pub mod polygon;
+pub mod property;
+pub mod random;
+pub mod ranges;
+pub mod snapshot;
 pub mod template;
+pub mod visualize;
 
 // Use this file to add helper functions and additional modules.
@@ -1,138 +1,251 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{parse, AppArguments};
+use advent_of_code::template::commands::{
+    all, compare, download, gen, profile, read, scaffold, solve, stats, time, viz,
+};
+use advent_of_code::visualize::Playback;
+use args::{parse, print_completions, AppArguments};
 
+#[cfg(feature = "today")]
+use advent_of_code::template::commands::race;
 #[cfg(feature = "today")]
 use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
+    use std::path::PathBuf;
+
     use advent_of_code::template::Day;
-    use std::process;
+    use clap::{Parser, Subcommand};
+    use clap_complete::Shell;
+
+    /// Solutions for Advent of Code in Rust.
+    #[derive(Parser)]
+    #[command(version, propagate_version = true)]
+    struct Cli {
+        #[command(subcommand)]
+        command: AppArguments,
+    }
 
+    #[derive(Subcommand)]
     pub enum AppArguments {
-        Download {
-            day: Day,
-        },
+        /// Download the input and puzzle description for a day via aoc-cli.
+        Download { day: Day },
+        /// Read the puzzle description for a day, from the copy saved by `download` if there is one.
         Read {
             day: Day,
+            /// Only show one part of the puzzle.
+            #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+            part: Option<u8>,
         },
+        /// Create the solution, input and example files for a day.
         Scaffold {
             day: Day,
+            /// Also download the input and puzzle description.
+            #[arg(long)]
             download: bool,
         },
+        /// Run the solution for a day against its real input.
         Solve {
             day: Day,
+            /// Run an optimized build.
+            #[arg(long)]
             release: bool,
+            /// Profile heap allocations with DHAT.
+            #[arg(long, conflicts_with = "release")]
             dhat: bool,
+            /// Submit the answer for the given part via aoc-cli.
+            #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
             submit: Option<u8>,
+            /// Save a picture of the puzzle as a `.png`, `.ppm` or animated `.gif`.
+            #[arg(long, value_name = "FILE")]
+            render: Option<PathBuf>,
+            /// Solve a random input of this size instead of the real one.
+            #[arg(long, value_name = "N", conflicts_with = "submit")]
+            size: Option<usize>,
+            /// The seed for the random input.
+            #[arg(long, default_value_t = 0, requires = "size")]
+            seed: u64,
         },
+        /// Run the solutions for all days.
         All {
+            /// Run an optimized build.
+            #[arg(long)]
             release: bool,
         },
+        /// Benchmark solutions and optionally store the timings in the readme.
         Time {
-            all: bool,
+            /// Benchmark a single day.
             day: Option<Day>,
+            /// Benchmark all days, including ones that are already benched.
+            #[arg(long, conflicts_with = "day")]
+            all: bool,
+            /// Store the timings in the readme.
+            #[arg(long)]
             store: bool,
+            /// Also measure the heap usage of each part with DHAT.
+            #[arg(long)]
+            memory: bool,
+            /// Benchmark on random inputs of this size instead of the real ones.
+            #[arg(long, value_name = "N", conflicts_with = "store")]
+            size: Option<usize>,
+            /// The seed for the random inputs.
+            #[arg(long, default_value_t = 0, requires = "size")]
+            seed: u64,
+        },
+        /// Print a random input for a day, for stress testing its solution.
+        Gen {
+            day: Day,
+            /// How big to make the input, roughly in lines or grid rows.
+            #[arg(long)]
+            size: usize,
+            /// The seed for the random input. The same seed always gives the same input.
+            #[arg(long, default_value_t = 0)]
+            seed: u64,
+        },
+        /// Show a progress report for all days.
+        Stats {
+            /// Show how long each part took to solve, as recorded by `today --race`.
+            #[arg(long)]
+            times: bool,
         },
+        /// Run every variant of a solution, check that their answers agree and compare their timings.
+        Compare { day: Day },
+        /// Profile one part of a solution and write a flamegraph to `data/profiles/`.
+        Profile {
+            day: Day,
+            /// The part to profile.
+            #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+            part: u8,
+            /// How long to run the part for, in seconds.
+            #[arg(long, default_value_t = 10)]
+            seconds: u64,
+        },
+        /// Show how a solution works, as an animation in the terminal.
+        Viz {
+            day: Day,
+            /// The part to show.
+            #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+            part: u8,
+            /// Use the example instead of the real input.
+            #[arg(long)]
+            example: bool,
+            /// How many frames to show each second.
+            #[arg(long, default_value_t = 10.0)]
+            fps: f64,
+            /// Wait for Enter before showing each frame.
+            #[arg(long)]
+            step: bool,
+            /// Leave out the colours.
+            #[arg(long)]
+            plain: bool,
+            /// Write the frames to a text file instead of showing them.
+            #[arg(long, value_name = "FILE")]
+            output: Option<PathBuf>,
+        },
+        /// Print a shell completion script to stdout.
+        Completions { shell: Shell },
+        /// Scaffold, download and read the puzzle for the current day.
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            /// Wait for the next puzzle to unlock first, and time how long each part takes. A
+            /// puzzle that unlocked less than an hour ago is opened straight away.
+            #[arg(long)]
+            race: bool,
+        },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
-
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
-
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
+    pub fn parse() -> AppArguments {
+        Cli::parse().command
+    }
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
+    pub fn print_completions(shell: Shell) {
+        use clap::CommandFactory;
 
-        Ok(app_args)
+        let mut cmd = Cli::command();
+        let name = cmd.get_name().to_string();
+        clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
     }
 }
 
 fn main() {
     match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+        AppArguments::All { release } => all::handle(release),
+        AppArguments::Time {
+            day,
+            all,
+            store,
+            memory,
+            size,
+            seed,
+        } => time::handle(day, all, store, memory, size, seed),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Read { day, part } => read::handle(day, part),
+        AppArguments::Scaffold { day, download } => {
+            scaffold::handle(day);
+            if download {
+                download::handle(day);
+            }
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
-                if download {
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            render,
+            size,
+            seed,
+        } => solve::handle(day, release, dhat, submit, render, size, seed),
+        AppArguments::Gen { day, size, seed } => gen::handle(day, size, seed),
+        AppArguments::Stats { times } => stats::handle(times),
+        AppArguments::Compare { day } => compare::handle(day),
+        AppArguments::Profile { day, part, seconds } => profile::handle(day, part, seconds),
+        AppArguments::Viz {
+            day,
+            part,
+            example,
+            fps,
+            step,
+            plain,
+            output,
+        } => viz::handle(
+            day,
+            part,
+            example,
+            Playback {
+                fps,
+                step,
+                plain,
+                output,
+            },
+        ),
+        AppArguments::Completions { shell } => print_completions(shell),
+        #[cfg(feature = "today")]
+        AppArguments::Today { race: true } => match Day::next_unlock() {
+            Some((day, wait)) => race::handle(day, wait),
+            None => {
+                eprintln!(
+                    "`today --race` can only wait for puzzles unlocking between the 1st and \
+                        the 25th of december."
+                );
+                process::exit(1)
+            }
+        },
+        #[cfg(feature = "today")]
+        AppArguments::Today { race: false } => {
+            match Day::today() {
+                Some(day) => {
+                    scaffold::handle(day);
                     download::handle(day);
+                    read::handle(day, None)
                 }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
+                None => {
+                    eprintln!(
+                        "`today` command can only be run between the 1st and \
                             the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
-        },
+                    );
+                    process::exit(1)
+                }
+            };
+        }
     };
 }
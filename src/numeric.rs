@@ -0,0 +1,416 @@
+/// Exact integer and rational arithmetic, for answers too big or too precise for floats.
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// The largest integer whose square is at most `n`.
+#[must_use]
+pub fn isqrt(n: u128) -> u128 {
+    // A float gets close, then fix up any rounding
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let mut root = (n as f64).sqrt() as u128;
+    while root.checked_mul(root).is_none_or(|square| square > n) {
+        root -= 1;
+    }
+    while (root + 1)
+        .checked_mul(root + 1)
+        .is_some_and(|square| square <= n)
+    {
+        root += 1;
+    }
+    root
+}
+
+/// The greatest common divisor, which is never negative.
+#[must_use]
+pub fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Whether `n` is prime, by trial division.
+#[must_use]
+pub fn is_prime(n: u64) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+/// A fraction in lowest terms, with a positive denominator. The `checked_` methods return
+/// `None` on overflow, while the operators panic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    /// Creates `numerator / denominator`, or `None` if the denominator is zero.
+    #[must_use]
+    pub fn new(numerator: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Some(Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    #[must_use]
+    pub const fn integer(n: i128) -> Self {
+        Self {
+            numerator: n,
+            denominator: 1,
+        }
+    }
+
+    #[must_use]
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    #[must_use]
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    /// The value as an integer, or `None` if it is a fraction.
+    #[must_use]
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    #[must_use]
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let denominator = self.denominator.checked_mul(other.denominator)?;
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator)?
+            .checked_add(other.numerator.checked_mul(self.denominator)?)?;
+        Self::new(numerator, denominator)
+    }
+
+    #[must_use]
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    #[must_use]
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // Cancel across first, to keep the products small
+        let a = gcd(self.numerator, other.denominator).max(1);
+        let b = gcd(other.numerator, self.denominator).max(1);
+        Self::new(
+            (self.numerator / a).checked_mul(other.numerator / b)?,
+            (self.denominator / b).checked_mul(other.denominator / a)?,
+        )
+    }
+
+    /// Divides, returning `None` on overflow or when dividing by zero.
+    #[must_use]
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        let flipped = Self::new(other.denominator, other.numerator)?;
+        self.checked_mul(flipped)
+    }
+
+    #[must_use]
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            numerator: self.numerator.checked_neg()?,
+            denominator: self.denominator,
+        })
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Self::integer(n)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self::integer(n.into())
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let left = self.numerator.checked_mul(other.denominator);
+        let right = other.numerator.checked_mul(self.denominator);
+        match (left, right) {
+            (Some(left), Some(right)) => left.cmp(&right),
+            _ => compare_fractions(
+                self.numerator,
+                self.denominator,
+                other.numerator,
+                other.denominator,
+            ),
+        }
+    }
+}
+
+/// Compares `a / b` with `c / d` without cross-multiplying, for when that would overflow. The
+/// whole parts are compared first, and then the flipped-over remainders, much like Euclid.
+fn compare_fractions(a: i128, b: i128, c: i128, d: i128) -> Ordering {
+    let whole = a.div_euclid(b).cmp(&c.div_euclid(d));
+    if whole != Ordering::Equal {
+        return whole;
+    }
+    match (a.rem_euclid(b), c.rem_euclid(d)) {
+        (0, 0) => Ordering::Equal,
+        (0, _) => Ordering::Less,
+        (_, 0) => Ordering::Greater,
+        // a / b < c / d exactly when d / c < b / a
+        (a, c) => compare_fractions(d, c, b, a),
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            _ => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+macro_rules! rational_op {
+    ($trait:ident, $method:ident, $checked:ident) => {
+        impl $trait for Rational {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self {
+                self.$checked(other).expect(concat!(
+                    "rational ",
+                    stringify!($method),
+                    " overflowed"
+                ))
+            }
+        }
+    };
+}
+
+rational_op!(Add, add, checked_add);
+rational_op!(Sub, sub, checked_sub);
+rational_op!(Mul, mul, checked_mul);
+rational_op!(Div, div, checked_div);
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg().expect("rational neg overflowed")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Given `values` of a polynomial at `0, 1, 2, ...`, the value at `x`, which can be past
+/// either end. Uses Newton's forward differences, so a polynomial of degree `d` needs
+/// `d + 1` values. Returns `None` on overflow.
+#[must_use]
+pub fn extrapolate(values: &[i128], x: i128) -> Option<i128> {
+    let mut differences = values.to_vec();
+    let mut result: i128 = 0;
+    // x choose k, which stays an integer even for negative x
+    let mut binomial: i128 = 1;
+    for k in 0..values.len() {
+        result = result.checked_add(differences[0].checked_mul(binomial)?)?;
+        binomial = binomial.checked_mul(x - k as i128)? / (k as i128 + 1);
+        for i in 0..differences.len() - 1 {
+            differences[i] = differences[i + 1].checked_sub(differences[i])?;
+        }
+        differences.pop();
+    }
+    Some(result)
+}
+
+/// The value at `x` of the lowest degree polynomial through `points`, which can be spaced
+/// however they like. Returns `None` on overflow, or if two points share an `x`.
+#[must_use]
+pub fn lagrange(points: &[(i128, i128)], x: i128) -> Option<Rational> {
+    let mut result = Rational::integer(0);
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let mut term = Rational::integer(yi);
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i != j {
+                term = term.checked_mul(Rational::new(x - xj, xi - xj)?)?;
+            }
+        }
+        result = result.checked_add(term)?;
+    }
+    Some(result)
+}
+
+/// Where the lines `a + t * u` and `b + s * v` cross, as `(t, s)`. `None` if they are parallel.
+#[must_use]
+pub fn line_intersection(
+    a: [i128; 2],
+    u: [i128; 2],
+    b: [i128; 2],
+    v: [i128; 2],
+) -> Option<(Rational, Rational)> {
+    let cross = |p: [i128; 2], q: [i128; 2]| p[0] * q[1] - p[1] * q[0];
+    let w = [b[0] - a[0], b[1] - a[1]];
+    let denominator = cross(u, v);
+    Some((
+        Rational::new(cross(w, v), denominator)?,
+        Rational::new(cross(w, u), denominator)?,
+    ))
+}
+
+/// Solves `rows * x = rhs` by Gauss-Jordan elimination, exactly. There can be more
+/// equations than unknowns, as long as they agree. Returns `None` if there is no single
+/// solution, or on overflow.
+#[must_use]
+pub fn solve_linear(rows: &[Vec<i128>], rhs: &[i128]) -> Option<Vec<Rational>> {
+    let unknowns = rows.first()?.len();
+    let mut matrix: Vec<Vec<Rational>> = rows
+        .iter()
+        .zip(rhs)
+        .map(|(row, &value)| row.iter().chain([&value]).map(|&n| n.into()).collect())
+        .collect();
+
+    for column in 0..unknowns {
+        let pivot = (column..matrix.len()).find(|&row| matrix[row][column].numerator != 0)?;
+        matrix.swap(column, pivot);
+        let divisor = matrix[column][column];
+        for value in &mut matrix[column] {
+            *value = value.checked_div(divisor)?;
+        }
+        let pivot_row = matrix[column].clone();
+        for row in (0..matrix.len()).filter(|&row| row != column) {
+            let factor = matrix[row][column];
+            if factor.numerator == 0 {
+                continue;
+            }
+            for (value, &pivot) in matrix[row].iter_mut().zip(&pivot_row).skip(column) {
+                *value = value.checked_sub(pivot.checked_mul(factor)?)?;
+            }
+        }
+    }
+
+    // Any equations left over have been reduced to 0 = something, which had better be 0
+    if matrix[unknowns..]
+        .iter()
+        .any(|row| row[unknowns].numerator != 0)
+    {
+        return None;
+    }
+    Some(
+        matrix
+            .iter()
+            .take(unknowns)
+            .map(|row| row[unknowns])
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(numerator: i128, denominator: i128) -> Rational {
+        Rational::new(numerator, denominator).unwrap()
+    }
+
+    #[test]
+    fn test_isqrt() {
+        let roots: Vec<_> = (0..10).map(isqrt).collect();
+        assert_eq!(roots, [0, 1, 1, 1, 2, 2, 2, 2, 2, 3]);
+        assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+        let big = 3_037_000_499u128 * 3_037_000_499;
+        assert_eq!(isqrt(big), 3_037_000_499);
+        assert_eq!(isqrt(big - 1), 3_037_000_498);
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes: Vec<u64> = (0..30).filter(|&n| is_prime(n)).collect();
+        assert_eq!(primes, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(is_prime(4_093));
+        assert!(!is_prime(4_095));
+    }
+
+    #[test]
+    fn test_rational() {
+        assert_eq!(r(2, -4), r(-1, 2));
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 2) - r(1, 3), r(1, 6));
+        assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+        assert_eq!(r(2, 3) / r(-4, 3), r(-1, 2));
+        assert_eq!(Rational::new(1, 0), None);
+        assert_eq!(r(1, 2).checked_div(Rational::integer(0)), None);
+        assert_eq!(r(6, 3).to_integer(), Some(2));
+        assert_eq!(r(7, 3).to_string(), "7/3");
+        assert_eq!(Rational::integer(i128::MAX).checked_add(r(1, 1)), None);
+    }
+
+    #[test]
+    fn test_rational_order() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        let huge = i128::MAX / 2;
+        assert!(r(huge, huge - 1) < r(huge - 1, huge - 2));
+        assert!(r(huge, 3) < r(huge, 2));
+        assert!(Rational::integer(huge) > r(huge - 1, 1));
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(extrapolate(&[0, 3, 6, 9, 12, 15], 6), Some(18));
+        assert_eq!(extrapolate(&[0, 3, 6, 9, 12, 15], -1), Some(-3));
+        assert_eq!(extrapolate(&[10, 13, 16, 21, 30, 45], -1), Some(5));
+        // n^2 + 1
+        assert_eq!(extrapolate(&[1, 2, 5], 1000), Some(1_000_001));
+        assert_eq!(extrapolate(&[1, 2, 5], -1000), Some(1_000_001));
+    }
+
+    #[test]
+    fn test_lagrange() {
+        // n^2 + 1, sampled unevenly
+        let points = [(-3, 10), (1, 2), (4, 17)];
+        assert_eq!(lagrange(&points, 1000), Some(Rational::integer(1_000_001)));
+        // a straight line through (0, 0) and (2, 1)
+        assert_eq!(lagrange(&[(0, 0), (2, 1)], 1), Some(r(1, 2)));
+        assert_eq!(lagrange(&[(0, 0), (0, 1)], 1), None);
+    }
+
+    #[test]
+    fn test_line_intersection() {
+        let (t, s) = line_intersection([19, 13], [-2, 1], [12, 31], [-1, -2]).unwrap();
+        assert_eq!((t, s), (r(64, 10), r(58, 10)));
+        assert_eq!(Rational::integer(19) + t * Rational::integer(-2), r(31, 5));
+        assert_eq!(line_intersection([0, 0], [1, 1], [1, 0], [2, 2]), None);
+    }
+
+    #[test]
+    fn test_solve_linear() {
+        // x + y = 3, x - y = 1, 2x = 4
+        let rows = [vec![1, 1], vec![1, -1], vec![2, 0]];
+        let integers = [2, 1].map(Rational::integer).to_vec();
+        assert_eq!(solve_linear(&rows, &[3, 1, 4]), Some(integers));
+        assert_eq!(solve_linear(&rows, &[3, 1, 5]), None);
+        // 2x = 1, with a zero to pivot past
+        let rows = [vec![0, 1], vec![2, 0]];
+        assert_eq!(solve_linear(&rows, &[7, 1]), Some(vec![r(1, 2), r(7, 1)]));
+        assert_eq!(solve_linear(&[vec![1, 1], vec![2, 2]], &[1, 2]), None);
+    }
+}
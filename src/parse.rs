@@ -0,0 +1,281 @@
+/// Helpers for picking apart puzzle input, with errors that say where the input went wrong.
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Returned when the input isn't what a parser expected, pointing at where it went wrong.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A piece of the input, along with the line and column (both counting from 1) it starts at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Span<'a> {
+    /// The whole input, starting at line 1, column 1.
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// An error pointing at the start of this span.
+    #[must_use]
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+
+    /// The span `bytes` bytes further in.
+    #[must_use]
+    pub fn skip(&self, bytes: usize) -> Self {
+        let skipped = &self.text[..bytes];
+        let (line, column) = match skipped.rfind('\n') {
+            Some(newline) => (
+                self.line + skipped.matches('\n').count(),
+                skipped[newline + 1..].chars().count() + 1,
+            ),
+            None => (self.line, self.column + skipped.chars().count()),
+        };
+        Self {
+            text: &self.text[bytes..],
+            line,
+            column,
+        }
+    }
+
+    /// The first `bytes` bytes of this span.
+    fn take(&self, bytes: usize) -> Self {
+        Self {
+            text: &self.text[..bytes],
+            ..*self
+        }
+    }
+
+    #[must_use]
+    pub fn trim(&self) -> Self {
+        let start = self.text.len() - self.text.trim_start().len();
+        let trimmed = self.skip(start);
+        trimmed.take(trimmed.text.trim_end().len())
+    }
+
+    /// Like [`str::split_once`], but an error says which separator was missing, and where.
+    pub fn split_once(&self, separator: &str) -> Result<(Self, Self), ParseError> {
+        let at = self
+            .text
+            .find(separator)
+            .ok_or_else(|| self.error(format!("expected {separator:?} in {:?}", self.text)))?;
+        Ok((self.take(at), self.skip(at + separator.len())))
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Self, ParseError> {
+        if self.text.starts_with(prefix) {
+            Ok(self.skip(prefix.len()))
+        } else {
+            Err(self.error(format!("expected {prefix:?}")))
+        }
+    }
+
+    pub fn strip_suffix(&self, suffix: &str) -> Result<Self, ParseError> {
+        match self.text.strip_suffix(suffix) {
+            Some(rest) => Ok(self.take(rest.len())),
+            None => Err(self
+                .skip(self.text.len())
+                .error(format!("expected {suffix:?}"))),
+        }
+    }
+
+    /// The pieces between each `separator`, like [`str::split`].
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Self> + 'a {
+        let mut rest = Some(*self);
+        std::iter::from_fn(move || {
+            let current = rest?;
+            match current.split_once(separator) {
+                Ok((piece, after)) => {
+                    rest = Some(after);
+                    Some(piece)
+                }
+                Err(_) => {
+                    rest = None;
+                    Some(current)
+                }
+            }
+        })
+    }
+
+    /// The lines of the span, ignoring a final newline.
+    pub fn lines(&self) -> impl Iterator<Item = Self> + 'a {
+        let text = self.text.strip_suffix('\n').unwrap_or(self.text);
+        self.take(text.len())
+            .split("\n")
+            .map(|line| line.take(line.text.trim_end_matches('\r').len()))
+    }
+
+    /// The blocks of lines separated by blank lines.
+    pub fn blocks(&self) -> impl Iterator<Item = Self> + 'a {
+        let text = self.text.trim_end_matches('\n');
+        self.take(text.len()).split("\n\n")
+    }
+
+    /// Parses the whole span, which must not have any surrounding whitespace.
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError>
+    where
+        T::Err: Display,
+    {
+        self.text
+            .parse()
+            .map_err(|e| self.error(format!("can't parse {:?}: {e}", self.text)))
+    }
+
+    /// Every run of digits in the span, ignoring everything else.
+    pub fn numbers<T: FromStr>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T::Err: Display,
+    {
+        self.find_numbers(false)
+    }
+
+    /// Every run of digits in the span, along with a `-` just before it.
+    pub fn signed_numbers<T: FromStr>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T::Err: Display,
+    {
+        self.find_numbers(true)
+    }
+
+    fn find_numbers<T: FromStr>(&self, signed: bool) -> Result<Vec<T>, ParseError>
+    where
+        T::Err: Display,
+    {
+        let bytes = self.text.as_bytes();
+        let mut numbers = Vec::new();
+        let mut rest = *self;
+        let mut offset = 0;
+        while offset < bytes.len() {
+            if !bytes[offset].is_ascii_digit() {
+                offset += 1;
+                continue;
+            }
+            let mut start = offset;
+            if signed && start > 0 && bytes[start - 1] == b'-' {
+                start -= 1;
+            }
+            let end = bytes[offset..]
+                .iter()
+                .position(|b| !b.is_ascii_digit())
+                .map_or(bytes.len(), |length| offset + length);
+
+            rest = rest.skip(start - (self.text.len() - rest.text.len()));
+            numbers.push(rest.take(end - start).parse()?);
+            offset = end;
+        }
+        Ok(numbers)
+    }
+
+    /// Collects exactly `N` items into an array, with an error here if there are more or fewer.
+    /// Destructure the array to get a tuple, e.g. `let [x, y] = span.array(span.numbers()?)?`.
+    pub fn array<T, const N: usize>(
+        &self,
+        items: impl IntoIterator<Item = T>,
+    ) -> Result<[T; N], ParseError> {
+        let items: Vec<T> = items.into_iter().collect();
+        let found = items.len();
+        items
+            .try_into()
+            .map_err(|_| self.error(format!("expected {N} items, found {found}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_once() {
+        let input = Span::new("Card 1: 41 48 | 83 86\nCard 2: 13");
+        let (card, numbers) = input.split_once(": ").unwrap();
+        assert_eq!(card.as_str(), "Card 1");
+        assert_eq!((numbers.line, numbers.column), (1, 9));
+
+        let second = input.lines().nth(1).unwrap();
+        let error = second.split_once("|").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            error.to_string(),
+            r#"line 2, column 1: expected "|" in "Card 2: 13""#
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        let line = Span::new("19, 13, 30 @ -2, 1, -2");
+        assert_eq!(line.numbers::<u32>(), Ok(vec![19, 13, 30, 2, 1, 2]));
+        assert_eq!(
+            line.signed_numbers::<i32>(),
+            Ok(vec![19, 13, 30, -2, 1, -2])
+        );
+        let [x, y, z] = line
+            .array(line.numbers::<u8>().unwrap().into_iter().take(3))
+            .unwrap();
+        assert_eq!((x, y, z), (19, 13, 30));
+
+        let error = Span::new("a\nb 1 300").numbers::<u8>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+    }
+
+    #[test]
+    fn test_array() {
+        let line = Span::new("1 2 3");
+        let error = line.array::<u32, 2>(line.numbers().unwrap()).unwrap_err();
+        assert_eq!(error.message, "expected 2 items, found 3");
+    }
+
+    #[test]
+    fn test_blocks_and_lines() {
+        let input = Span::new("a\r\nb\n\nc{d}\n");
+        let blocks: Vec<_> = input.blocks().collect();
+        assert_eq!(blocks.len(), 2);
+        let lines: Vec<_> = blocks[0].lines().map(|l| l.as_str()).collect();
+        assert_eq!(lines, ["a", "b"]);
+        assert_eq!((blocks[1].line, blocks[1].column), (4, 1));
+
+        let inner = blocks[1]
+            .strip_prefix("c{")
+            .and_then(|s| s.strip_suffix("}"))
+            .unwrap();
+        assert_eq!((inner.as_str(), inner.column), ("d", 3));
+        let error = blocks[1].strip_suffix(")").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 5));
+        assert_eq!(Span::new("  x ").trim().column, 3);
+    }
+}
@@ -0,0 +1,216 @@
+/// Simple polygons with corners on the integer lattice, such as loops traced through a grid.
+use crate::geometry::{Direction, Point};
+use crate::numeric::gcd;
+
+/// Which way a polygon's vertices go round, on a grid where rows grow downwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    Anticlockwise,
+}
+
+/// A closed polygon, with an edge from each vertex to the next and from the last back to the first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point<i64>>,
+}
+
+impl Polygon {
+    /// Creates a polygon from its vertices in order. A final vertex repeating the first is dropped.
+    #[must_use]
+    pub fn new(mut vertices: Vec<Point<i64>>) -> Self {
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Self { vertices }
+    }
+
+    /// Traces a polygon from the origin, moving `distance` in `direction` for every edge.
+    pub fn from_steps(steps: impl IntoIterator<Item = (Direction, i64)>) -> Self {
+        let mut current: Point<i64> = Point::new(0, 0);
+        let mut vertices = vec![current];
+        for (direction, distance) in steps {
+            current = current.step_n(direction, distance);
+            vertices.push(current);
+        }
+        Self::new(vertices)
+    }
+
+    #[must_use]
+    pub fn vertices(&self) -> &[Point<i64>] {
+        &self.vertices
+    }
+
+    /// Each edge as a pair of its end points.
+    pub fn edges(&self) -> impl Iterator<Item = (Point<i64>, Point<i64>)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the area, positive when the vertices go clockwise. Doubling keeps it an integer.
+    ///
+    /// <https://en.wikipedia.org/wiki/Shoelace_formula>
+    #[must_use]
+    pub fn signed_double_area(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| a.column * b.row - b.column * a.row)
+            .sum()
+    }
+
+    /// The area enclosed by the edges, which may end in a half.
+    #[must_use]
+    pub fn area(&self) -> f64 {
+        self.signed_double_area().abs() as f64 / 2.0
+    }
+
+    /// The orientation, or `None` if the polygon has no area.
+    #[must_use]
+    pub fn orientation(&self) -> Option<Orientation> {
+        match self.signed_double_area() {
+            0 => None,
+            a if a > 0 => Some(Orientation::Clockwise),
+            _ => Some(Orientation::Anticlockwise),
+        }
+    }
+
+    /// The number of lattice points on the edges.
+    #[must_use]
+    pub fn boundary_points(&self) -> i64 {
+        let points: i128 = self
+            .edges()
+            .map(|(a, b)| gcd(i128::from(b.row - a.row), i128::from(b.column - a.column)))
+            .sum();
+        i64::try_from(points).expect("boundary points should fit in an i64")
+    }
+
+    /// The number of lattice points strictly inside the polygon.
+    ///
+    /// <https://en.wikipedia.org/wiki/Pick%27s_theorem>
+    #[must_use]
+    pub fn interior_points(&self) -> i64 {
+        (self.signed_double_area().abs() - self.boundary_points() + 2) / 2
+    }
+
+    /// The number of lattice points inside the polygon or on its edges.
+    #[must_use]
+    pub fn lattice_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Is `point` on one of the edges?
+    #[must_use]
+    pub fn on_boundary(&self, point: Point<i64>) -> bool {
+        self.edges().any(|(a, b)| {
+            let cross = (b.row - a.row) * (point.column - a.column)
+                - (b.column - a.column) * (point.row - a.row);
+            cross == 0
+                && point.row >= a.row.min(b.row)
+                && point.row <= a.row.max(b.row)
+                && point.column >= a.column.min(b.column)
+                && point.column <= a.column.max(b.column)
+        })
+    }
+
+    /// Is `point` strictly inside the polygon? Points on the edges are not.
+    #[must_use]
+    pub fn contains(&self, point: Point<i64>) -> bool {
+        if self.on_boundary(point) {
+            return false;
+        }
+
+        // Count edges crossed by a ray heading east from the point
+        self.edges()
+            .filter(|(a, b)| (a.row > point.row) != (b.row > point.row))
+            .filter(|(a, b)| {
+                let rows = b.row - a.row;
+                let left = (point.column - a.column) * rows;
+                let right = (point.row - a.row) * (b.column - a.column);
+                if rows > 0 {
+                    left < right
+                } else {
+                    left > right
+                }
+            })
+            .count()
+            % 2
+            == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Direction::*;
+
+    fn square() -> Polygon {
+        Polygon::from_steps([(East, 4), (South, 4), (West, 4), (North, 4)])
+    }
+
+    #[test]
+    fn test_area_and_points() {
+        let square = square();
+        assert_eq!(square.vertices().len(), 4);
+        assert_eq!(square.signed_double_area(), 32);
+        assert_eq!(square.area(), 16.0);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+        assert_eq!(square.lattice_points(), 25);
+
+        let triangle = Polygon::new(vec![Point::new(0, 0), Point::new(0, 3), Point::new(1, 0)]);
+        assert_eq!(triangle.area(), 1.5);
+        assert_eq!(triangle.boundary_points(), 5);
+        assert_eq!(triangle.interior_points(), 0);
+    }
+
+    #[test]
+    fn test_orientation() {
+        let square = square();
+        assert_eq!(square.orientation(), Some(Orientation::Clockwise));
+        let mut vertices = square.vertices().to_vec();
+        vertices.reverse();
+        assert_eq!(
+            Polygon::new(vertices).orientation(),
+            Some(Orientation::Anticlockwise)
+        );
+        let line = Polygon::new(vec![Point::new(0, 0), Point::new(0, 3)]);
+        assert_eq!(line.orientation(), None);
+    }
+
+    #[test]
+    fn test_contains() {
+        // A U shape, with a notch two wide and two deep cut into the top
+        let u = Polygon::from_steps([
+            (East, 2),
+            (South, 2),
+            (East, 2),
+            (North, 2),
+            (East, 2),
+            (South, 4),
+            (West, 6),
+            (North, 4),
+        ]);
+        let inside = (0..=4)
+            .flat_map(|row| (0..=6).map(move |column| Point::new(row, column)))
+            .filter(|&point| u.contains(point))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            inside,
+            [
+                (1, 1),
+                (1, 5),
+                (2, 1),
+                (2, 5),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (3, 4),
+                (3, 5)
+            ]
+            .map(Point::from)
+        );
+        assert_eq!(inside.len() as i64, u.interior_points());
+        assert!(u.on_boundary(Point::new(2, 3)));
+        assert!(!u.contains(Point::new(1, 3)));
+        assert!(!u.on_boundary(Point::new(1, 3)));
+    }
+}
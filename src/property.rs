@@ -0,0 +1,313 @@
+/// Property testing: checks that something holds for lots of random values, and shrinks any
+/// value it fails for down to a small one that still fails, so the failure is easy to read.
+use std::env;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::random::Rng;
+
+/// Something that can be made at random for a property to check, and made simpler when the
+/// property fails for it.
+pub trait Arbitrary: Clone + Debug {
+    /// A random value, with `size` limiting how big it gets.
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self;
+
+    /// Values a little simpler than this one, the most promising first. Each has to be
+    /// strictly simpler, so that shrinking comes to an end.
+    fn shrink(&self) -> Vec<Self> {
+        Vec::new()
+    }
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng, _size: usize) -> Self {
+        rng.chance(0.5)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self {
+            vec![false]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl Arbitrary for usize {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        rng.index(size + 1)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let n = *self;
+        let mut smaller = vec![0, n / 2, n.saturating_sub(1)];
+        smaller.dedup();
+        smaller.retain(|&s| s < n);
+        smaller
+    }
+}
+
+impl Arbitrary for u64 {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        rng.below(size as u64 + 1)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        (*self as usize)
+            .shrink()
+            .into_iter()
+            .map(|s| s as u64)
+            .collect()
+    }
+}
+
+impl Arbitrary for i64 {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        rng.between(-(size as i64), size as i64)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let n = *self;
+        let mut smaller = vec![0, n / 2, n - n.signum()];
+        if n < 0 {
+            smaller.insert(1, -n);
+        }
+        smaller.dedup();
+        smaller.retain(|&s| s != n);
+        smaller
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        (0..rng.index(size + 1))
+            .map(|_| T::arbitrary(rng, size))
+            .collect()
+    }
+
+    /// Tries dropping half of the items, then each item, then shrinking each item.
+    fn shrink(&self) -> Vec<Self> {
+        let mut smaller = Vec::new();
+        if self.len() > 1 {
+            let half = self.len() / 2;
+            smaller.push(self[..half].to_vec());
+            smaller.push(self[half..].to_vec());
+        }
+        for i in 0..self.len() {
+            let mut fewer = self.clone();
+            fewer.remove(i);
+            smaller.push(fewer);
+        }
+        for (i, item) in self.iter().enumerate() {
+            for simpler in item.shrink() {
+                let mut changed = self.clone();
+                changed[i] = simpler;
+                smaller.push(changed);
+            }
+        }
+        smaller
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary> Arbitrary for (A, B) {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        (A::arbitrary(rng, size), B::arbitrary(rng, size))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b) = self;
+        let firsts = a.shrink().into_iter().map(|a| (a, b.clone()));
+        let seconds = b.shrink().into_iter().map(|b| (a.clone(), b));
+        firsts.chain(seconds).collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// `Ok` if the brute force and the real solution give the same answer, otherwise what each
+/// of them said.
+pub fn agree<R: PartialEq + Debug>(oracle: R, solution: R) -> Result<(), String> {
+    if oracle == solution {
+        Ok(())
+    } else {
+        Err(format!(
+            "the oracle gave {oracle:?} but the solution gave {solution:?}"
+        ))
+    }
+}
+
+/// A value a property failed for, after shrinking it as far as it would go.
+#[derive(Debug)]
+pub struct Failure<T> {
+    /// Which of the random cases failed first, counting from 0.
+    pub case: usize,
+    pub value: T,
+    pub message: String,
+    pub shrinks: usize,
+}
+
+/// How many random values to check a property with, and how they are drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Config {
+    pub cases: usize,
+    /// The size of the last case. Cases grow up to it, so simple values are tried first.
+    pub max_size: usize,
+    pub seed: u64,
+}
+
+impl Config {
+    /// 100 cases up to size 30 from seed 0, unless `PROPERTY_CASES` or `PROPERTY_SEED` say
+    /// otherwise. Failures print the seed so they can be repeated.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let var = |name: &str| env::var(name).ok().and_then(|v| v.parse().ok());
+        Self {
+            cases: var("PROPERTY_CASES").map_or(100, |cases| cases as usize),
+            max_size: 30,
+            seed: var("PROPERTY_SEED").unwrap_or(0),
+        }
+    }
+
+    #[must_use]
+    pub fn cases(self, cases: usize) -> Self {
+        Self { cases, ..self }
+    }
+
+    #[must_use]
+    pub fn max_size(self, max_size: usize) -> Self {
+        Self { max_size, ..self }
+    }
+
+    /// The first random value that `property` fails for, shrunk. A panic counts as failing.
+    pub fn find_failure<T, P>(&self, property: P) -> Option<Failure<T>>
+    where
+        T: Arbitrary,
+        P: Fn(&T) -> Result<(), String>,
+    {
+        let run = |value: &T| match panic::catch_unwind(AssertUnwindSafe(|| property(value))) {
+            Ok(result) => result,
+            Err(payload) => Err(payload
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .map_or("panicked".to_string(), |m| format!("panicked: {m}"))),
+        };
+
+        for case in 0..self.cases {
+            let mut rng = Rng::new(self.seed.wrapping_add(case as u64));
+            let size = self.max_size * (case + 1) / self.cases.max(1);
+            let value = T::arbitrary(&mut rng, size);
+            let Err(message) = run(&value) else {
+                continue;
+            };
+
+            // Keep taking the first simpler value that still fails
+            let mut failure = Failure {
+                case,
+                value,
+                message,
+                shrinks: 0,
+            };
+            'shrinking: while failure.shrinks < 10_000 {
+                for simpler in failure.value.shrink() {
+                    if let Err(message) = run(&simpler) {
+                        failure.value = simpler;
+                        failure.message = message;
+                        failure.shrinks += 1;
+                        continue 'shrinking;
+                    }
+                }
+                break;
+            }
+            return Some(failure);
+        }
+        None
+    }
+
+    /// Panics with the smallest value it can find that `property` fails for, if any.
+    pub fn check<T, P>(&self, property: P)
+    where
+        T: Arbitrary,
+        P: Fn(&T) -> Result<(), String>,
+    {
+        if let Some(failure) = self.find_failure(property) {
+            panic!(
+                "property failed on case {} of {} (PROPERTY_SEED={}), shrunk {} times to\n{:#?}\n{}",
+                failure.case + 1,
+                self.cases,
+                self.seed,
+                failure.shrinks,
+                failure.value,
+                failure.message,
+            );
+        }
+    }
+}
+
+/// Checks `property` with the [`Config::from_env`] settings.
+pub fn check<T, P>(property: P)
+where
+    T: Arbitrary,
+    P: Fn(&T) -> Result<(), String>,
+{
+    Config::from_env().check(property);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passing_property() {
+        check(|v: &Vec<usize>| agree(v.iter().rev().rev().count(), v.len()));
+    }
+
+    #[test]
+    fn test_shrinks_to_smallest() {
+        let failure = Config::from_env()
+            .find_failure(|v: &Vec<usize>| {
+                if v.iter().sum::<usize>() < 10 {
+                    Ok(())
+                } else {
+                    Err("too big".to_string())
+                }
+            })
+            .unwrap();
+        // Greedy shrinking stops where no smaller step fails, without merging items
+        assert_eq!(failure.value.iter().sum::<usize>(), 10);
+        assert_eq!(failure.message, "too big");
+        assert!(failure.shrinks > 0);
+    }
+
+    #[test]
+    fn test_panics_count_as_failures() {
+        let failure = Config::from_env()
+            .find_failure(|&(a, b): &(i64, i64)| {
+                assert!(a <= 3 || b >= 0, "{a} and {b}");
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(failure.value, (4, -1));
+        assert_eq!(failure.message, "panicked: 4 and -1");
+    }
+
+    #[test]
+    fn test_same_seed_same_failure() {
+        let property = |n: &u64| agree(n % 7 == 6, false);
+        let config = Config::from_env().cases(50);
+        let first = config.find_failure(property).unwrap();
+        let again = config.find_failure(property).unwrap();
+        assert_eq!((first.case, first.value), (again.case, again.value));
+        assert_eq!(first.value, 6);
+    }
+
+    #[test]
+    fn test_shrink_is_simpler() {
+        assert_eq!(10_usize.shrink(), vec![0, 5, 9]);
+        assert_eq!((-6_i64).shrink(), vec![0, 6, -3, -5]);
+        assert_eq!(0_usize.shrink(), Vec::<usize>::new());
+        assert_eq!(vec![true].shrink(), vec![vec![], vec![false]]);
+    }
+}
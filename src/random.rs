@@ -0,0 +1,258 @@
+/// Seeded random numbers for generating puzzle inputs, so the same seed always gives the same input.
+use std::collections::HashSet;
+
+use crate::grid::Pos;
+
+/// A small, fast generator using `SplitMix64`. Not for anything that needs to be unpredictable.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, which must not be empty.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "can't pick from an empty range");
+        // The top bits of the product are close enough to uniform for inputs
+        #[allow(clippy::cast_possible_truncation)]
+        let picked = ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64;
+        picked
+    }
+
+    /// An index into something of length `n`.
+    pub fn index(&mut self, n: usize) -> usize {
+        self.below(n as u64) as usize
+    }
+
+    /// A number from `low` to `high`, including both.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "{low} is above {high}");
+        let span = (i128::from(high) - i128::from(low) + 1) as u128;
+        let offset = if span > u128::from(u64::MAX) {
+            self.next_u64()
+        } else {
+            self.below(span as u64)
+        };
+        (i128::from(low) + i128::from(offset)) as i64
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        #[allow(clippy::cast_precision_loss)]
+        let unit = (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64;
+        unit < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+
+    /// `count` different lowercase words of `len` letters.
+    pub fn names(&mut self, count: usize, len: usize) -> Vec<String> {
+        assert!(
+            (count as f64) <= 26_f64.powi(len as i32),
+            "not enough {len} letter names for {count}"
+        );
+        let mut seen = HashSet::new();
+        let mut names = Vec::with_capacity(count);
+        while names.len() < count {
+            let name: String = (0..len)
+                .map(|_| char::from(b'a' + self.below(26) as u8))
+                .collect();
+            if seen.insert(name.clone()) {
+                names.push(name);
+            }
+        }
+        names
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The steps north, east, south and west, in the order that [`random_loop`] numbers them.
+const DELTAS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// A random closed loop that never crosses itself, on a grid of `2 * height` by `2 * width`
+/// cells, as the cells it visits in order.
+///
+/// The loop runs around the outside of a random tree on a `height` by `width` grid, which
+/// is trimmed back to about `fill` of the cells so the loop winds in and out rather than
+/// filling the whole grid. Every loop cell is next to the tree, so nothing is left between
+/// the loop's sides; scale it up to make room inside.
+pub fn random_loop(rng: &mut Rng, height: usize, width: usize, fill: f64) -> Vec<Pos> {
+    let cells = height * width;
+    assert!(cells > 0, "the loop needs a cell to go around");
+    let neighbour = |cell: usize, direction: usize| {
+        let (row, column) = (cell / width, cell % width);
+        let (dr, dc) = DELTAS[direction];
+        let (row, column) = (row.checked_add_signed(dr)?, column.checked_add_signed(dc)?);
+        (row < height && column < width).then_some(row * width + column)
+    };
+
+    // Grow a spanning tree from a random cell, joining on a random edge each time
+    let mut links = vec![[false; 4]; cells];
+    let mut in_tree = vec![false; cells];
+    let start = rng.index(cells);
+    in_tree[start] = true;
+    let mut frontier: Vec<(usize, usize)> = (0..4).map(|d| (start, d)).collect();
+    while !frontier.is_empty() {
+        let (cell, direction) = frontier.swap_remove(rng.index(frontier.len()));
+        let Some(next) = neighbour(cell, direction) else {
+            continue;
+        };
+        if in_tree[next] {
+            continue;
+        }
+        in_tree[next] = true;
+        links[cell][direction] = true;
+        links[next][(direction + 2) % 4] = true;
+        frontier.extend((0..4).map(|d| (next, d)));
+    }
+
+    // Trim random leaves until few enough cells are left
+    let degree = |links: &[bool; 4]| links.iter().filter(|&&l| l).count();
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let target = ((cells as f64 * fill).round() as usize).clamp(1, cells);
+    let mut remaining = cells;
+    let mut leaves: Vec<usize> = (0..cells).filter(|&c| degree(&links[c]) == 1).collect();
+    while remaining > target && !leaves.is_empty() {
+        let leaf = leaves.swap_remove(rng.index(leaves.len()));
+        if !in_tree[leaf] || degree(&links[leaf]) != 1 {
+            continue;
+        }
+        let direction = links[leaf].iter().position(|&l| l).unwrap();
+        let next = neighbour(leaf, direction).unwrap();
+        links[leaf][direction] = false;
+        links[next][(direction + 2) % 4] = false;
+        in_tree[leaf] = false;
+        remaining -= 1;
+        if degree(&links[next]) == 1 {
+            leaves.push(next);
+        }
+    }
+
+    // Each tree cell is two by two loop cells, which turn back on themselves where the
+    // tree has no branch and carry on into the next cell where it does
+    let mut arms: Vec<Option<[usize; 2]>> = vec![None; cells * 4];
+    let fine = |row: usize, column: usize| row * width * 2 + column;
+    for cell in (0..cells).filter(|&c| in_tree[c]) {
+        let [north, east, south, west] = links[cell];
+        let pick = |linked: bool, yes: usize, no: usize| if linked { yes } else { no };
+        let (row, column) = (cell / width * 2, cell % width * 2);
+        arms[fine(row, column)] = Some([pick(north, 0, 1), pick(west, 3, 2)]);
+        arms[fine(row, column + 1)] = Some([pick(north, 0, 3), pick(east, 1, 2)]);
+        arms[fine(row + 1, column)] = Some([pick(south, 2, 1), pick(west, 3, 0)]);
+        arms[fine(row + 1, column + 1)] = Some([pick(south, 2, 3), pick(east, 1, 0)]);
+    }
+
+    let first = (0..cells).find(|&c| in_tree[c]).unwrap();
+    let first = (first / width * 2, first % width * 2);
+    let mut path = vec![first];
+    let mut came_from = None;
+    loop {
+        let (row, column) = *path.last().unwrap();
+        let direction = arms[fine(row, column)]
+            .unwrap()
+            .into_iter()
+            .find(|&d| Some(d) != came_from)
+            .unwrap();
+        let (dr, dc) = DELTAS[direction];
+        let next = (
+            row.checked_add_signed(dr).unwrap(),
+            column.checked_add_signed(dc).unwrap(),
+        );
+        if next == first {
+            return path;
+        }
+        came_from = Some((direction + 2) % 4);
+        path.push(next);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_numbers() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+        let first: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        assert_eq!(first, (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(first, (0..10).map(|_| c.next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!(rng.below(7) < 7);
+            assert!((-3..=3).contains(&rng.between(-3, 3)));
+        }
+        assert_eq!(rng.between(5, 5), 5);
+        rng.between(i64::MIN, i64::MAX);
+
+        let seen: HashSet<i64> = (0..1000).map(|_| rng.between(1, 6)).collect();
+        assert_eq!(seen.len(), 6);
+    }
+
+    #[test]
+    fn test_shuffle_keeps_items() {
+        let mut rng = Rng::new(7);
+        let mut items: Vec<usize> = (0..50).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_names_are_different() {
+        let names = Rng::new(3).names(600, 2);
+        assert_eq!(names.iter().collect::<HashSet<_>>().len(), 600);
+        assert!(names.iter().all(|n| n.len() == 2));
+    }
+
+    #[test]
+    fn test_random_loop_is_closed() {
+        for seed in 0..20 {
+            let mut rng = Rng::new(seed);
+            let path = random_loop(&mut rng, 6, 9, 0.6);
+            let cells: HashSet<Pos> = path.iter().copied().collect();
+            assert_eq!(cells.len(), path.len(), "no cell is visited twice");
+            for (i, &(r1, c1)) in path.iter().enumerate() {
+                let (r2, c2) = path[(i + 1) % path.len()];
+                assert_eq!(r1.abs_diff(r2) + c1.abs_diff(c2), 1);
+                assert!(r1 < 12 && c1 < 18);
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_cell_loop() {
+        let path = random_loop(&mut Rng::new(0), 1, 1, 1.0);
+        assert_eq!(path, vec![(0, 0), (0, 1), (1, 1), (1, 0)]);
+    }
+}
@@ -0,0 +1,266 @@
+/// Sets of values stored as ranges, and piecewise mappings between them.
+use std::iter::Sum;
+use std::ops::{Add, Range, Sub};
+
+/// A set of values, stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds all values in `range` to the set.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let at = self.ranges.partition_point(|r| r.end < range.start);
+        let mut merged = range;
+        while at < self.ranges.len() && self.ranges[at].start <= merged.end {
+            let r = self.ranges.remove(at);
+            merged = merged.start.min(r.start)..merged.end.max(r.end);
+        }
+        self.ranges.insert(at, merged);
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        let at = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(at).is_some_and(|r| r.contains(&value))
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest value in the set.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// The ranges making up the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in &other.ranges {
+            result.insert(range.clone());
+        }
+        result
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        for range in &self.ranges {
+            let mut start = range.start;
+            for cut in other
+                .ranges
+                .iter()
+                .filter(|cut| cut.start < range.end && cut.end > range.start)
+            {
+                if cut.start > start {
+                    ranges.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Splits the set into the values below `at`, and those at or above it.
+    #[must_use]
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let (mut below, mut above) = (Vec::new(), Vec::new());
+        for range in &self.ranges {
+            if range.end <= at {
+                below.push(range.clone());
+            } else if range.start >= at {
+                above.push(range.clone());
+            } else {
+                below.push(range.start..at);
+                above.push(at..range.end);
+            }
+        }
+        (Self { ranges: below }, Self { ranges: above })
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Sum> RangeSet<T> {
+    /// The number of values in the set.
+    #[must_use]
+    pub fn len(&self) -> T {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A piecewise mapping that moves values in source ranges by an offset, and leaves all
+/// other values unchanged. If source ranges overlap, the one added first wins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeMap<T> {
+    entries: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> RangeMap<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the values in `source` onto the range starting at `destination`.
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        self.entries.push((source, destination));
+    }
+
+    /// Maps a single value.
+    #[must_use]
+    pub fn get(&self, value: T) -> T {
+        self.entries
+            .iter()
+            .find(|(source, _)| source.contains(&value))
+            .map_or(value, |(source, destination)| {
+                *destination + (value - source.start)
+            })
+    }
+
+    /// Maps every value in `set` at once, working on whole ranges.
+    #[must_use]
+    pub fn map(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        let mut remaining = set.clone();
+        let mut result = RangeSet::new();
+
+        for (source, destination) in &self.entries {
+            let shift = |value: T| *destination + (value - source.start);
+            let cut = RangeSet::from(source.clone());
+            for hit in remaining.intersection(&cut).iter() {
+                result.insert(shift(hit.start)..shift(hit.end));
+            }
+            remaining = remaining.difference(&cut);
+        }
+
+        result.union(&remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<u32>]) -> RangeSet<u32> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let s = set(&[5..8, 1..3, 3..4, 7..10, 12..12]);
+        assert_eq!(s.iter().cloned().collect::<Vec<_>>(), [1..4, 5..10]);
+        assert_eq!(s.len(), 8);
+        assert!(s.contains(9));
+        assert!(!s.contains(4));
+        assert_eq!(s.min(), Some(1));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[0..10, 20..30]);
+        let b = RangeSet::from(5..25);
+        assert_eq!(a.union(&b), RangeSet::from(0..30));
+        assert_eq!(a.intersection(&b), set(&[5..10, 20..25]));
+        assert_eq!(a.difference(&b), set(&[0..5, 25..30]));
+        assert_eq!(b.difference(&a), RangeSet::from(10..20));
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_split_at() {
+        let (below, above) = set(&[0..10, 20..30]).split_at(25);
+        assert_eq!(below, set(&[0..10, 20..25]));
+        assert_eq!(above, RangeSet::from(25..30));
+        let (below, above) = RangeSet::from(0..10).split_at(0);
+        assert!(below.is_empty());
+        assert_eq!(above, RangeSet::from(0..10));
+    }
+
+    #[test]
+    fn test_range_map() {
+        let mut map = RangeMap::new();
+        map.insert(98..100, 50);
+        map.insert(50..98, 52);
+        assert_eq!(map.get(98), 50);
+        assert_eq!(map.get(53), 55);
+        assert_eq!(map.get(10), 10);
+        assert_eq!(map.get(100), 100);
+        assert_eq!(map.map(&RangeSet::from(79..93)), RangeSet::from(81..95));
+        assert_eq!(
+            map.map(&RangeSet::from(90..102)),
+            set(&[50..52, 92..100, 100..102])
+        );
+    }
+}
@@ -0,0 +1,131 @@
+/// Snapshot testing: compares something's `Display` output with a copy saved under
+/// `data/snapshots`, so a test can check a whole grid or pile instead of a single number.
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Set to anything but `0` to save snapshots that are missing or don't match, instead of failing.
+pub const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// Checks that `value` shows up the same as it did when the snapshot called `name` was saved,
+/// like `assert_snapshot!("day14_north", rocks)`. Run a new test with `UPDATE_SNAPSHOTS=1`
+/// to save its snapshot, and review the file before checking it in. The same accepts a
+/// change.
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $value:expr) => {
+        $crate::snapshot::check(&$name, &$value.to_string())
+    };
+}
+
+/// The directory snapshots are saved in.
+#[must_use]
+pub fn dir() -> PathBuf {
+    env::current_dir().unwrap().join("data").join("snapshots")
+}
+
+/// The file in `dir` that the snapshot called `name` is saved in.
+#[must_use]
+pub fn path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.snap"))
+}
+
+fn updating() -> bool {
+    env::var(UPDATE_VAR).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// The lines that differ between the saved and the new snapshot, marked with `-` and `+`.
+fn diff(saved: &str, actual: &str) -> String {
+    let (saved, actual): (Vec<&str>, Vec<&str>) =
+        (saved.lines().collect(), actual.lines().collect());
+    let mut diff = String::new();
+    for i in 0..saved.len().max(actual.len()) {
+        match (saved.get(i), actual.get(i)) {
+            (Some(old), Some(new)) if old == new => {}
+            (old, new) => {
+                if let Some(old) = old {
+                    writeln!(diff, "{:>4} - {old}", i + 1).unwrap();
+                }
+                if let Some(new) = new {
+                    writeln!(diff, "{:>4} + {new}", i + 1).unwrap();
+                }
+            }
+        }
+    }
+    diff
+}
+
+/// Compares `actual` with the snapshot called `name`, saving it if it's missing or different
+/// and `UPDATE_SNAPSHOTS` is set. Panics with the lines that changed otherwise. Used by
+/// [`assert_snapshot!`](crate::assert_snapshot).
+pub fn check(name: &str, actual: &str) {
+    compare(&dir(), name, actual, updating());
+}
+
+fn compare(dir: &Path, name: &str, actual: &str, update: bool) {
+    let path = path(dir, name);
+    let save = || {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual)
+            .unwrap_or_else(|e| panic!("could not save snapshot {}: {e}", path.display()));
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(saved) if saved == actual => {}
+        _ if update => save(),
+        Ok(saved) => panic!(
+            "snapshot {name} doesn't match {}, set {UPDATE_VAR}=1 to accept the change\n{}",
+            path.display(),
+            diff(&saved, actual)
+        ),
+        Err(_) => panic!(
+            "snapshot {name} is missing from {}, set {UPDATE_VAR}=1 to save it\n{actual}",
+            path.display()
+        ),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("a\nb\nc\n", "a\nB\nc\n"), "   2 - b\n   2 + B\n");
+        assert_eq!(diff("a\n", "a\nb\n"), "   2 + b\n");
+        assert_eq!(diff("same", "same"), "");
+    }
+
+    fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+        let error = std::panic::catch_unwind(f).unwrap_err();
+        error.downcast_ref::<String>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_saves_then_checks() {
+        let dir = env::temp_dir().join(format!("aoc-snapshots-{}", std::process::id()));
+        let (name, path) = ("saves_then_checks", path(&dir, "saves_then_checks"));
+
+        let missing = panic_message(|| compare(&dir, name, "first\n", false));
+        assert!(missing.contains("is missing"), "{missing}");
+        assert!(!path.exists());
+
+        compare(&dir, name, "first\n", true);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\n");
+        compare(&dir, name, "first\n", false);
+
+        let changed = panic_message(|| compare(&dir, name, "second\n", false));
+        compare(&dir, name, "third\n", true);
+        let saved = fs::read_to_string(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(saved, "third\n");
+        assert!(
+            changed.contains("   1 - first\n   1 + second\n"),
+            "{changed}"
+        );
+    }
+}
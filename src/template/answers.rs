@@ -0,0 +1,322 @@
+use std::{collections::HashMap, convert::Infallible, fmt, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// The answer to one part of a puzzle.
+///
+/// Parts can return any integer type or a string, which all convert into an `Answer`.
+/// Text that reads as an integer is stored as one, so an answer compares and hashes
+/// the same whether it came from a solution, the output of a binary or `answers.json`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        match value.parse() {
+            Ok(n) => Answer::Integer(n),
+            Err(_) => Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        match value.parse() {
+            Ok(n) => Answer::Integer(n),
+            Err(_) => Answer::Text(value),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Answer::from(s))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Represents the answers a solution produced for a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnswerRecord {
+    pub day: Day,
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+}
+
+/// Represents the answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<AnswerRecord>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        let Ok(s) = fs::read_to_string(ANSWERS_FILE_PATH) else {
+            return Answers::default();
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    /// Merge two sets of answers part by part, preferring the answers in `new`.
+    pub fn merge(&self, new: &Self) -> Self {
+        let mut data = self.data.clone();
+
+        for answer in &new.data {
+            match data.iter_mut().find(|a| a.day == answer.day) {
+                Some(existing) => {
+                    if answer.part_1.is_some() {
+                        existing.part_1.clone_from(&answer.part_1);
+                    }
+                    if answer.part_2.is_some() {
+                        existing.part_2.clone_from(&answer.part_2);
+                    }
+                }
+                None => data.push(answer.clone()),
+            }
+        }
+
+        data.sort_unstable_by_key(|a| a.day);
+        Answers { data }
+    }
+
+    /// Returns the answers stored for `day`, if any.
+    pub fn get(&self, day: Day) -> Option<&AnswerRecord> {
+        self.data.iter().find(|a| a.day == day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(AnswerRecord::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Answers are written as strings, as JSON numbers can't hold every integer exactly.
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        JsonValue::String(value.to_string())
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        match value {
+            JsonValue::String(s) => Ok(Answer::from(s.as_str())),
+            #[allow(clippy::cast_possible_truncation)]
+            JsonValue::Number(n) if n.fract() == 0.0 => Ok(Answer::Integer(*n as i128)),
+            _ => Err("Expected answer to be a string or an integer.".into()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AnswerRecord> for JsonValue {
+    fn from(value: &AnswerRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AnswerRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part = |key: &str| match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(v) => Answer::try_from(v).map(Some),
+            None => Err(format!("Expected answer.{key} to be null or an answer.")),
+        };
+
+        Ok(AnswerRecord {
+            day,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, AnswerRecord, Answers};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "142", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(
+            answers.data,
+            vec![AnswerRecord {
+                day: day!(1),
+                part_1: Some(Answer::Integer(142)),
+                part_2: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn reads_numbers_and_text_answers() {
+        let json = r#"{ "data": [{ "day": "25", "part_1": 54, "part_2": "Push the button" }] }"#
+            .to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.data[0].part_1, Some(Answer::Integer(54)));
+        assert_eq!(
+            answers.data[0].part_2,
+            Some(Answer::Text("Push the button".into()))
+        );
+    }
+
+    #[test]
+    fn answers_compare_by_value() {
+        assert_eq!(Answer::from(142_u32), Answer::from(142_usize));
+        assert_eq!(Answer::from(-3_i64), "-3".parse().unwrap());
+        assert_eq!(Answer::from("281".to_string()), Answer::Integer(281));
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(
+            JsonValue::from(&Answer::from(167_409_079_868_000_u64)),
+            JsonValue::String("167409079868000".into())
+        );
+    }
+
+    #[test]
+    fn merges_answers_by_part() {
+        let stored = Answers {
+            data: vec![AnswerRecord {
+                day: day!(2),
+                part_1: Some(8.into()),
+                part_2: Some(2286.into()),
+            }],
+        };
+        let new = Answers {
+            data: vec![
+                AnswerRecord {
+                    day: day!(2),
+                    part_1: Some(9.into()),
+                    part_2: None,
+                },
+                AnswerRecord {
+                    day: day!(1),
+                    part_1: None,
+                    part_2: Some(281.into()),
+                },
+            ],
+        };
+
+        let merged = stored.merge(&new);
+        assert_eq!(merged.data.len(), 2);
+        assert_eq!(merged.data[0].day, day!(1));
+        assert_eq!(merged.data[1].part_1, Some(9.into()));
+        assert_eq!(merged.data[1].part_2, Some(2286.into()));
+    }
+}
@@ -1,5 +1,9 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{all_days, answers::Answers, run_multi::run_multi, runner::RunnerArgs};
 
 pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+    let (answers, _) = run_multi(&all_days().collect(), is_release, &RunnerArgs::default());
+
+    if let Err(e) = Answers::read_from_file().merge(&answers).store_file() {
+        eprintln!("Failed to store answers: {e}");
+    }
 }
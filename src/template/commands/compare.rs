@@ -0,0 +1,33 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::{runner::RunnerArgs, Day};
+
+pub fn handle(day: Day) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+    ];
+
+    cmd_args.extend(
+        RunnerArgs {
+            compare: true,
+            ..Default::default()
+        }
+        .to_args(),
+    );
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -0,0 +1,36 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::{runner::RunnerArgs, Day};
+
+/// Prints a random input for `day`, made by the `generate` function of its solution.
+pub fn handle(day: Day, size: usize, seed: u64) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+    ];
+
+    cmd_args.extend(
+        RunnerArgs {
+            size: Some(size),
+            seed,
+            print_input: true,
+            ..Default::default()
+        }
+        .to_args(),
+    );
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -1,6 +1,12 @@
 pub mod all;
+pub mod compare;
 pub mod download;
+pub mod gen;
+pub mod profile;
+pub mod race;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod stats;
 pub mod time;
+pub mod viz;
@@ -0,0 +1,35 @@
+use std::process::{Command, Stdio};
+
+use crate::template::{runner::RunnerArgs, Day};
+
+pub fn handle(day: Day, part: u8, seconds: u64) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--profile".to_string(),
+        "profiling".to_string(),
+        "--features".to_string(),
+        "cpu-profile".to_string(),
+        "--".to_string(),
+    ];
+
+    cmd_args.extend(
+        RunnerArgs {
+            profile: Some(part),
+            seconds,
+            ..Default::default()
+        }
+        .to_args(),
+    );
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
@@ -0,0 +1,43 @@
+use std::io::{stdout, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::template::commands::{download, read, scaffold};
+use crate::template::solve_times::{self, SolveTimes};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Waits for `day` to unlock, then scaffolds it, downloads the puzzle, records when it was
+/// opened and shows it. Submitting with `cargo solve NN --submit N` records each part's time.
+pub fn handle(day: Day, wait: Duration) {
+    countdown(day, wait);
+    scaffold::handle(day);
+    download::handle(day);
+
+    let mut times = SolveTimes::read_from_file();
+    times.open(day, solve_times::now());
+    if let Err(e) = times.store_file() {
+        eprintln!("failed to store solve times: {e}");
+    }
+
+    read::handle(day, None);
+}
+
+fn countdown(day: Day, wait: Duration) {
+    let unlock = Instant::now() + wait;
+    loop {
+        let left = unlock.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            break;
+        }
+        // Round up, so the countdown reaches zero as the puzzle unlocks
+        let shown = Duration::from_secs(left.as_secs() + u64::from(left.subsec_nanos() > 0));
+        print!(
+            "\rDay {day} unlocks in {ANSI_BOLD}{}{ANSI_RESET} ",
+            solve_times::format_elapsed(shown)
+        );
+        stdout().flush().unwrap();
+        // Sleep until the shown time changes
+        thread::sleep(left + Duration::from_secs(1) - shown);
+    }
+    println!("\rDay {day} is unlocked!        ");
+}
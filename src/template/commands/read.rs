@@ -1,8 +1,33 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_cli, markdown, Day};
 
-pub fn handle(day: Day) {
+/// Shows the puzzle description saved by `cargo download`, or fetches it with aoc-cli if
+/// there isn't one yet. `part` picks one half of the description.
+pub fn handle(day: Day, part: Option<u8>) {
+    let path = aoc_cli::get_puzzle_path(day);
+    let Ok(puzzle) = fs::read_to_string(&path) else {
+        if part.is_some() {
+            eprintln!("No puzzle description at \"{path}\", run `cargo download {day}` first.");
+            process::exit(1);
+        }
+        return read_online(day);
+    };
+
+    let text = match part {
+        Some(part) => markdown::part(&puzzle, part),
+        None => Some(puzzle.as_str()),
+    };
+    let Some(text) = text else {
+        eprintln!(
+            "\"{path}\" has no part two yet, run `cargo download {day}` again after solving part one."
+        );
+        process::exit(1);
+    };
+    print!("{}", markdown::render(text, markdown::WIDTH));
+}
+
+fn read_online(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -1,6 +1,6 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
     process,
 };
 
@@ -9,20 +9,40 @@ use crate::template::Day;
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// The binary for a day, which runs the solution in its module.
+const BIN_TEMPLATE: &str = "use advent_of_code::days::day%DAY%::*;
+
+advent_of_code::solution!(%DAY_NUMBER%);
+";
+
+const DAYS_MODULE: &str = "src/days/mod.rs";
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Adds `pub mod dayNN;` to the list of day modules, keeping them in order.
+fn register_module(day: Day) -> io::Result<()> {
+    let declaration = format!("pub mod day{day};");
+    let existing = fs::read_to_string(DAYS_MODULE)?;
+    let mut lines: Vec<&str> = existing.lines().collect();
+    if !lines.contains(&declaration.as_str()) {
+        lines.push(&declaration);
+        lines.sort_unstable();
+    }
+    fs::write(DAYS_MODULE, lines.join("\n") + "\n")
+}
 
-    let mut file = match safe_create_file(&module_path) {
+fn write_template(path: &str, template: &str, day: Day) {
+    let mut file = match safe_create_file(path) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
@@ -30,19 +50,32 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let contents = template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string());
+    match file.write_all(contents.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{path}\"");
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
             process::exit(1);
         }
     }
+}
+
+pub fn handle(day: Day) {
+    let input_path = format!("data/inputs/{day}.txt");
+    let example_path = format!("data/examples/{day}.txt");
+    let module_path = format!("src/days/day{day}.rs");
+    let bin_path = format!("src/bin/{day}.rs");
+
+    write_template(&module_path, MODULE_TEMPLATE, day);
+    write_template(&bin_path, BIN_TEMPLATE, day);
+    if let Err(e) = register_module(day) {
+        eprintln!("Failed to add the module to \"{DAYS_MODULE}\": {e}");
+        process::exit(1);
+    }
 
     match create_file(&input_path) {
         Ok(_) => {
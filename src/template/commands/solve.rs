@@ -1,8 +1,17 @@
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{runner::RunnerArgs, Day};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    render: Option<PathBuf>,
+    size: Option<usize>,
+    seed: u64,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -17,11 +26,16 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
-
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
+    cmd_args.extend(
+        RunnerArgs {
+            submit: submit_part,
+            render,
+            size,
+            seed,
+            ..Default::default()
+        }
+        .to_args(),
+    );
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
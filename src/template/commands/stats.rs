@@ -0,0 +1,113 @@
+use crate::template::solve_times::{format_elapsed, SolveTimes};
+use crate::template::stats::{collect, DayStats};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+fn mark(done: bool) -> &'static str {
+    if done {
+        "✔"
+    } else {
+        "✖"
+    }
+}
+
+fn format_timing(stats: &DayStats) -> String {
+    match &stats.timing {
+        Some(timing) => format!(
+            "{} / {}",
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-")
+        ),
+        None => "-".into(),
+    }
+}
+
+fn format_row(cells: [&str; 8]) -> String {
+    let [day, code, input, examples, part_1, part_2, tests, timing] = cells;
+    format!("{day:<5}{code:^6}{input:^7}{examples:^10}{part_1:^8}{part_2:^8}{tests:^7}  {timing}")
+}
+
+/// Shows how long each part took after the puzzle was opened, and part two after part one.
+fn print_times() {
+    let times = SolveTimes::read_from_file();
+    if times.data.is_empty() {
+        println!("No solve times yet. Open a puzzle with `cargo today --race` and submit with `cargo solve <day> --submit <part>`.");
+        return;
+    }
+
+    let format = |elapsed: Option<std::time::Duration>| elapsed.map_or("-".into(), format_elapsed);
+    println!(
+        "{ANSI_BOLD}{:<5}{:>10}{:>10}{:>10}{ANSI_RESET}",
+        "Day", "Part 1", "Part 2", "Delta"
+    );
+    for time in &times.data {
+        println!(
+            "{:<5}{:>10}{:>10}{:>10}",
+            time.day.to_string(),
+            format(time.duration(1)),
+            format(time.duration(2)),
+            format(time.delta()),
+        );
+    }
+}
+
+pub fn handle(times: bool) {
+    if times {
+        print_times();
+        return;
+    }
+
+    let days = collect();
+
+    println!(
+        "{ANSI_BOLD}{}{ANSI_RESET}",
+        format_row(["Day", "Code", "Input", "Examples", "Part 1", "Part 2", "Tests", "Timing"])
+    );
+
+    for stats in &days {
+        println!(
+            "{}",
+            format_row([
+                &stats.day.to_string(),
+                mark(stats.scaffolded),
+                mark(stats.input),
+                &stats.examples.to_string(),
+                mark(stats.part_1.is_some()),
+                mark(stats.part_2.is_some()),
+                &stats.tests.to_string(),
+                &format_timing(stats),
+            ])
+        );
+    }
+
+    let count = |f: fn(&DayStats) -> bool| days.iter().filter(|s| f(s)).count();
+    let stars = count(|s| s.part_1.is_some()) + count(|s| s.part_2.is_some());
+    let total_millis = days
+        .iter()
+        .filter_map(|s| s.timing.as_ref())
+        .fold(0.0, |total, t| total + t.total_nanos)
+        / 1_000_000_f64;
+
+    println!();
+    println!(
+        "{ANSI_BOLD}Total:{ANSI_RESET} {} scaffolded, {} inputs, {} examples, {stars}/50 stars, {} tests, {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}",
+        count(|s| s.scaffolded),
+        count(|s| s.input),
+        days.iter().map(|s| s.examples).sum::<usize>(),
+        days.iter().map(|s| s.tests).sum::<usize>(),
+    );
+
+    let missing: Vec<_> = days
+        .iter()
+        .filter(|s| s.scaffolded)
+        .map(|s| (s.day, s.missing()))
+        .filter(|(_, missing)| !missing.is_empty())
+        .collect();
+
+    if !missing.is_empty() {
+        println!();
+        println!("{ANSI_BOLD}Missing:{ANSI_RESET}");
+        for (day, missing) in missing {
+            println!("Day {day}: {}", missing.join(", "));
+        }
+    }
+}
@@ -1,15 +1,26 @@
 use std::collections::HashSet;
 
-use crate::template::run_multi::run_multi;
+use crate::template::answers::Answers;
+use crate::template::run_multi::{run_multi, run_multi_memory};
+use crate::template::runner::RunnerArgs;
 use crate::template::timings::Timings;
 use crate::template::{all_days, readme_benchmarks, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Benchmarks the solutions. With a `size`, they run on random inputs from their generators,
+/// and the timings are only shown, never stored.
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    memory: bool,
+    size: Option<usize>,
+    seed: u64,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            if run_all || size.is_some() {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -21,9 +32,25 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let args = RunnerArgs {
+        time: true,
+        size,
+        seed,
+        ..Default::default()
+    };
+    let (answers, timings) = run_multi(&days_to_run, true, &args);
+    let mut timings = timings.unwrap();
+
+    if memory {
+        run_multi_memory(&days_to_run, &args, &mut timings);
+    }
+
+    if store && size.is_none() {
+        Answers::read_from_file()
+            .merge(&answers)
+            .store_file()
+            .unwrap();
 
-    if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
@@ -32,8 +59,8 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
@@ -0,0 +1,40 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::{runner::RunnerArgs, Day};
+use crate::visualize::Playback;
+
+pub fn handle(day: Day, part: u8, example: bool, playback: Playback) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+    ];
+
+    cmd_args.extend(
+        RunnerArgs {
+            viz: Some(part),
+            example,
+            fps: playback.fps,
+            step: playback.step,
+            plain: playback.plain,
+            output: playback.output,
+            ..Default::default()
+        }
+        .to_args(),
+    );
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .stdin(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
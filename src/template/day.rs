@@ -1,13 +1,20 @@
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
+#[cfg(feature = "today")]
+use std::time::Duration;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, Days, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
+/// How long after a puzzle unlocks `today --race` still opens it, instead of waiting for the
+/// next one.
+#[cfg(feature = "today")]
+const LATE_START: Duration = Duration::from_secs(60 * 60);
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -55,6 +62,33 @@ impl Day {
             None
         }
     }
+
+    /// Returns the day to race and how long until it unlocks, if it's between the 1st and the
+    /// 25th of december: the one that unlocked at midnight on the server if that was less
+    /// than an hour ago, and the one unlocking at the next midnight otherwise.
+    pub fn next_unlock() -> Option<(Self, Duration)> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        Self::unlock_after(Utc::now().with_timezone(&offset))
+    }
+
+    fn unlock_after(now: DateTime<FixedOffset>) -> Option<(Self, Duration)> {
+        let midnight = now
+            .date_naive()
+            .and_hms_opt(0, 0, 0)?
+            .and_local_timezone(*now.offset())
+            .single()?;
+        let since = (now - midnight).to_std().ok()?;
+        let (unlock, wait) = if since < LATE_START {
+            (midnight, Duration::ZERO)
+        } else {
+            let next = midnight.checked_add_days(Days::new(1))?;
+            (next, (next - now).to_std().ok()?)
+        };
+        if unlock.month() != 12 || unlock.day() > 25 {
+            return None;
+        }
+        Some((Self::new(u8::try_from(unlock.day()).ok()?)?, wait))
+    }
 }
 
 impl Display for Day {
@@ -189,4 +223,45 @@ mod tests {
     }
 }
 
+#[cfg(all(test, feature = "today"))]
+mod unlock_tests {
+    use super::{Day, SERVER_UTC_OFFSET};
+    use chrono::{DateTime, FixedOffset, TimeZone};
+    use std::time::Duration;
+
+    fn server_time(month: u32, day: u32, hour: u32, min: u32) -> DateTime<FixedOffset> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).unwrap();
+        offset
+            .with_ymd_and_hms(2023, month, day, hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn unlocks_at_server_midnight() {
+        assert_eq!(
+            Day::unlock_after(server_time(11, 30, 23, 58)),
+            Some((Day(1), Duration::from_secs(120)))
+        );
+        assert_eq!(
+            Day::unlock_after(server_time(12, 7, 1, 0)),
+            Some((Day(8), Duration::from_secs(23 * 3600)))
+        );
+        assert_eq!(Day::unlock_after(server_time(12, 25, 23, 0)), None);
+    }
+
+    #[test]
+    fn opens_a_puzzle_that_just_unlocked() {
+        assert_eq!(
+            Day::unlock_after(server_time(12, 7, 0, 1)),
+            Some((Day(7), Duration::ZERO))
+        );
+        assert_eq!(
+            Day::unlock_after(server_time(12, 1, 0, 59)),
+            Some((Day(1), Duration::ZERO))
+        );
+        assert_eq!(Day::unlock_after(server_time(12, 26, 0, 10)), None);
+        assert_eq!(Day::unlock_after(server_time(6, 1, 12, 0)), None);
+    }
+}
+
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,441 @@
+/// Renders the puzzle descriptions that aoc-cli saves as markdown, for reading in a terminal.
+use std::mem;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// How wide paragraphs are wrapped.
+pub const WIDTH: usize = 80;
+
+/// The site makes `<em>` text glow, so it's shown bright white here.
+const ANSI_HIGHLIGHT: &str = "\x1b[97m";
+const ANSI_CODE: &str = "\x1b[32m";
+
+/// The heading that starts the second half of a puzzle, once part one is solved.
+const PART_TWO: &str = "--- Part Two ---";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Style {
+    bold: bool,
+    italic: bool,
+    highlight: bool,
+    code: bool,
+}
+
+impl Style {
+    fn ansi(self) -> String {
+        let codes = [
+            (self.bold, ANSI_BOLD),
+            (self.italic, ANSI_ITALIC),
+            (self.code, ANSI_CODE),
+            (self.highlight, ANSI_HIGHLIGHT),
+        ];
+        codes
+            .into_iter()
+            .filter(|&(on, _)| on)
+            .map(|(_, code)| code)
+            .collect()
+    }
+}
+
+/// Runs of text in one style.
+type Pieces = Vec<(Style, String)>;
+
+fn push(out: &mut Pieces, style: Style, text: &str) {
+    match out.last_mut() {
+        Some((last, pieces)) if *last == style => pieces.push_str(text),
+        _ => out.push((style, text.to_string())),
+    }
+}
+
+/// The name of the HTML tag at the start of `s`, like `em` or `/em`, and the rest after it.
+fn tag(s: &str) -> Option<(&str, &str)> {
+    let (inside, rest) = s.strip_prefix('<')?.split_once('>')?;
+    let name = inside.split([' ', '/']).find(|name| !name.is_empty())?;
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) || inside.contains('<') {
+        return None;
+    }
+    let closing = inside.starts_with('/');
+    Some((
+        if closing {
+            &inside[..name.len() + 1]
+        } else {
+            name
+        },
+        rest,
+    ))
+}
+
+fn entity(s: &str) -> Option<(&str, &str)> {
+    [
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&amp;", "&"),
+        ("&quot;", "\""),
+        ("&#39;", "'"),
+        ("&nbsp;", " "),
+    ]
+    .into_iter()
+    .find_map(|(name, text)| Some((text, s.strip_prefix(name)?)))
+}
+
+/// Splits a line into styled runs: `*emphasis*`, `**strong**`, `` `code` ``, `<em>` and
+/// `<code>` tags, and links, which keep only their text. AoC marks answers as `` `*42*` ``,
+/// so code that's all emphasis is highlighted too.
+fn inline(mut rest: &str, mut style: Style, out: &mut Pieces) {
+    while let Some(c) = rest.chars().next() {
+        if let Some(escaped) = rest.strip_prefix('\\').and_then(|s| s.chars().next()) {
+            push(out, style, &escaped.to_string());
+            rest = &rest[1 + escaped.len_utf8()..];
+        } else if let Some((code, after)) = rest.strip_prefix('`').and_then(|s| s.split_once('`')) {
+            let highlighted = code.len() > 2 && code.starts_with('*') && code.ends_with('*');
+            let code = if highlighted {
+                &code[1..code.len() - 1]
+            } else {
+                code
+            };
+            let code_style = Style {
+                code: true,
+                highlight: style.highlight || highlighted,
+                ..style
+            };
+            push(out, code_style, code);
+            rest = after;
+        } else if let Some(after) = rest
+            .strip_prefix("**")
+            .filter(|s| style.bold || !s.starts_with(char::is_whitespace) && s.contains("**"))
+        {
+            style.bold = !style.bold;
+            rest = after;
+        } else if let Some(after) = rest
+            .strip_prefix('*')
+            .filter(|s| style.italic || !s.starts_with(char::is_whitespace) && s.contains('*'))
+        {
+            style.italic = !style.italic;
+            rest = after;
+        } else if let Some((text, after)) = rest
+            .strip_prefix('[')
+            .and_then(|s| s.split_once("]("))
+            .and_then(|(text, s)| Some((text, s.split_once(')')?.1)))
+        {
+            inline(text, style, out);
+            rest = after;
+        } else if let Some((name, after)) = tag(rest) {
+            match name {
+                "em" | "/em" => style.highlight = name == "em",
+                "code" | "/code" => style.code = name == "code",
+                _ => {}
+            }
+            rest = after;
+        } else if let Some((text, after)) = entity(rest) {
+            push(out, style, text);
+            rest = after;
+        } else {
+            push(out, style, &c.to_string());
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+}
+
+/// Wraps styled text into lines no wider than `width`, starting the first with `first` and
+/// the rest with `indent`. A word longer than the width gets a line of its own.
+fn wrap(pieces: &Pieces, width: usize, first: &str, indent: &str) -> Vec<String> {
+    let mut words: Vec<Vec<(Style, &str)>> = Vec::new();
+    let mut word = Vec::new();
+    for (style, text) in pieces {
+        for (i, part) in text.split(char::is_whitespace).enumerate() {
+            if i > 0 && !word.is_empty() {
+                words.push(mem::take(&mut word));
+            }
+            if !part.is_empty() {
+                word.push((*style, part));
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    let mut lines = Vec::new();
+    let mut line = Line::new(first);
+    for word in words {
+        let length: usize = word.iter().map(|(_, text)| text.chars().count()).sum();
+        if !line.empty && line.used + 1 + length > width {
+            lines.push(mem::replace(&mut line, Line::new(indent)).finish());
+        }
+        if !line.empty {
+            line.text.push(' ');
+            line.used += 1;
+        }
+        for (style, text) in word {
+            line.push(style, text);
+        }
+        line.used += length;
+        line.empty = false;
+    }
+    if !line.empty {
+        lines.push(line.finish());
+    }
+    lines
+}
+
+/// A line being wrapped, which only switches colours where the style changes.
+struct Line {
+    text: String,
+    used: usize,
+    empty: bool,
+    style: Style,
+}
+
+impl Line {
+    fn new(indent: &str) -> Self {
+        Self {
+            text: indent.to_string(),
+            used: indent.chars().count(),
+            empty: true,
+            style: Style::default(),
+        }
+    }
+
+    fn push(&mut self, style: Style, text: &str) {
+        if style != self.style {
+            if self.style != Style::default() {
+                self.text.push_str(ANSI_RESET);
+            }
+            self.text.push_str(&style.ansi());
+            self.style = style;
+        }
+        self.text.push_str(text);
+    }
+
+    fn finish(mut self) -> String {
+        self.push(Style::default(), "");
+        self.text
+    }
+}
+
+fn styled(text: &str, style: Style) -> Pieces {
+    let mut pieces = Vec::new();
+    inline(text, style, &mut pieces);
+    pieces
+}
+
+/// A line like `# Title`.
+fn atx_heading(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    (text.len() < line.len() && line.len() - text.len() <= 6 && text.starts_with(' '))
+        .then(|| text.trim().trim_end_matches('#').trim_end())
+}
+
+/// A line of `---` or `===` under a heading's text.
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && (line.bytes().all(|b| b == b'-') || line.bytes().all(|b| b == b'='))
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    ["* ", "- ", "+ "]
+        .into_iter()
+        .find_map(|marker| line.strip_prefix(marker))
+}
+
+fn fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+/// Renders a puzzle description for the terminal: bold headings, highlighted answers, code
+/// blocks in colour and paragraphs and lists wrapped to `width`.
+#[must_use]
+pub fn render(markdown: &str, width: usize) -> String {
+    let heading = Style {
+        bold: true,
+        ..Style::default()
+    };
+    let mut blocks = Blocks::default();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut item: Option<Vec<&str>> = None;
+    let flush = |blocks: &mut Blocks, paragraph: &mut Vec<&str>, item: &mut Option<Vec<&str>>| {
+        if !paragraph.is_empty() {
+            let text = mem::take(paragraph).join(" ");
+            blocks.push(wrap(&styled(&text, Style::default()), width, "", ""), false);
+        }
+        if let Some(lines) = item.take() {
+            let text = lines.join(" ");
+            blocks.push(
+                wrap(&styled(&text, Style::default()), width, "  • ", "    "),
+                true,
+            );
+        }
+    };
+
+    let mut lines = markdown.lines().peekable();
+    while let Some(line) = lines.next() {
+        if fence(line) {
+            flush(&mut blocks, &mut paragraph, &mut item);
+            let code = lines
+                .by_ref()
+                .take_while(|line| !fence(line))
+                .map(|line| format!("    {ANSI_CODE}{line}{ANSI_RESET}"))
+                .collect();
+            blocks.push(code, false);
+        } else if let Some(text) = atx_heading(line).or_else(|| {
+            (paragraph.is_empty() && !line.trim().is_empty())
+                .then_some(line)
+                .filter(|_| lines.peek().is_some_and(|next| is_underline(next)))
+        }) {
+            flush(&mut blocks, &mut paragraph, &mut item);
+            if atx_heading(line).is_none() {
+                lines.next();
+            }
+            blocks.push(wrap(&styled(text.trim(), heading), width, "", ""), false);
+        } else if let Some(text) = list_item(line) {
+            flush(&mut blocks, &mut paragraph, &mut item);
+            item = Some(vec![text]);
+        } else if line.trim().is_empty() {
+            flush(&mut blocks, &mut paragraph, &mut item);
+        } else if let Some(lines) = &mut item {
+            lines.push(line.trim());
+        } else {
+            paragraph.push(line.trim());
+        }
+    }
+    flush(&mut blocks, &mut paragraph, &mut item);
+
+    let blocks: Vec<String> = blocks
+        .0
+        .into_iter()
+        .filter(|(_, block)| !block.is_empty())
+        .map(|(_, block)| block.join("\n"))
+        .collect();
+    blocks.join("\n\n") + "\n"
+}
+
+/// The rendered blocks, which are separated by blank lines, and whether each is a list.
+#[derive(Default)]
+struct Blocks(Vec<(bool, Vec<String>)>);
+
+impl Blocks {
+    /// Adds a block, or more items to the list just before it.
+    fn push(&mut self, lines: Vec<String>, list: bool) {
+        match self.0.last_mut() {
+            Some((true, items)) if list => items.extend(lines),
+            _ => self.0.push((list, lines)),
+        }
+    }
+}
+
+/// The half of a puzzle description about `part`: everything before the part two heading
+/// for part 1, and everything from it for part 2, or `None` if that isn't there yet.
+#[must_use]
+pub fn part(markdown: &str, part: u8) -> Option<&str> {
+    let start = markdown
+        .find(PART_TWO)
+        .map(|i| markdown[..i].rfind('\n').map_or(0, |n| n + 1));
+    match (part, start) {
+        (1, Some(start)) => Some(&markdown[..start]),
+        (1, None) => Some(markdown),
+        (_, Some(start)) => Some(&markdown[start..]),
+        (_, None) => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "\\--- Day 1: Trebuchet?! ---
+----------
+
+Something is wrong with global snow production, and you've been selected to take a look.
+The Elves have even given you a [map](/2023/day/1).
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+Adding these together produces `*142*`.
+
+Your puzzle answer was `55712`.
+
+\\--- Part Two ---
+----------
+
+Your calculation isn't quite right. It looks like some of the digits are actually
+<em>spelled out with letters</em>:
+
+* `one`, `two`
+* and so on
+";
+
+    #[test]
+    fn test_render() {
+        let code = |s: &str| format!("{ANSI_CODE}{s}{ANSI_RESET}");
+        let expected = [
+            format!("{ANSI_BOLD}--- Day 1: Trebuchet?! ---{ANSI_RESET}"),
+            String::new(),
+            "Something is wrong with global snow production, and you've been selected to take"
+                .to_string(),
+            "a look. The Elves have even given you a map.".to_string(),
+            String::new(),
+            "For example:".to_string(),
+            String::new(),
+            format!("    {}", code("1abc2")),
+            format!("    {}", code("pqr3stu8vwx")),
+            String::new(),
+            format!("Adding these together produces {ANSI_CODE}{ANSI_HIGHLIGHT}142{ANSI_RESET}."),
+            String::new(),
+            format!("Your puzzle answer was {}.", code("55712")),
+        ];
+        let rendered = render(part(PUZZLE, 1).unwrap(), WIDTH);
+        assert_eq!(rendered, expected.join("\n") + "\n");
+    }
+
+    #[test]
+    fn test_render_part_two() {
+        let rendered = render(part(PUZZLE, 2).unwrap(), 40);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], format!("{ANSI_BOLD}--- Part Two ---{ANSI_RESET}"));
+        assert_eq!(lines[2], "Your calculation isn't quite right. It");
+        assert_eq!(
+            lines[4],
+            format!("actually {ANSI_HIGHLIGHT}spelled out with letters{ANSI_RESET}:")
+        );
+        assert_eq!(
+            &lines[6..],
+            [
+                format!("  • {ANSI_CODE}one{ANSI_RESET}, {ANSI_CODE}two{ANSI_RESET}"),
+                "  • and so on".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_part() {
+        assert!(part(PUZZLE, 1).unwrap().ends_with("`55712`.\n\n"));
+        assert!(part(PUZZLE, 2).unwrap().starts_with("\\--- Part Two"));
+        assert_eq!(part("Just part one\n", 1), Some("Just part one\n"));
+        assert_eq!(part("Just part one\n", 2), None);
+    }
+
+    #[test]
+    fn test_inline() {
+        let pieces = styled(
+            "a **b** *c* 2 * 3 <span title=\"x\">d</span> &lt;e&gt; \\*f",
+            Style::default(),
+        );
+        let text: String = pieces.iter().map(|(_, text)| text.as_str()).collect();
+        assert_eq!(text, "a b c 2 * 3 d <e> *f");
+        assert!(pieces.contains(&(
+            Style {
+                bold: true,
+                ..Style::default()
+            },
+            "b".to_string()
+        )));
+    }
+}
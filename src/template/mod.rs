@@ -4,11 +4,16 @@ pub mod aoc_cli;
 pub mod commands;
 pub mod runner;
 
+pub use answers::Answer;
 pub use day::*;
 
+mod answers;
 mod day;
+mod markdown;
 mod readme_benchmarks;
 mod run_multi;
+mod solve_times;
+mod stats;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -24,6 +29,66 @@ pub fn read_file(folder: &str, day: Day) -> String {
     f.expect("could not open input file")
 }
 
+/// Runs a part on the example file `data/examples/{day}{suffix}.txt` and checks that it gives
+/// `expected`, failing with the file's path and the error the part reported, if any. The
+/// expected answer has the part's type, so a long literal needs no suffix.
+/// Used by [`example_tests!`](crate::example_tests).
+pub fn check_example<T: Into<Answer>>(
+    day: Day,
+    part: u8,
+    suffix: &str,
+    solve: impl Fn(&str) -> Option<T>,
+    expected: T,
+) {
+    let path = format!("data/examples/{day}{suffix}.txt");
+    let input = fs::read_to_string(env::current_dir().unwrap().join(&path))
+        .unwrap_or_else(|e| panic!("could not open example {path}: {e}"));
+    let expected = expected.into();
+    match solve(&input).map(Into::into) {
+        Some(answer) if answer == expected => {}
+        Some(answer) => panic!("part {part} on {path}: expected {expected}, got {answer}"),
+        None => panic!(
+            "part {part} on {path}: expected {expected}, got no answer{}",
+            runner::take_error().map_or(String::new(), |error| format!(" ({error})"))
+        ),
+    }
+}
+
+/// Generates a test for each example a day's answers are checked against, from rows of
+/// `(part, suffix, expected)`. The suffix picks the example file, so
+/// `example_tests!((1, "", 142), (2, "-2", 281))` runs `part_one` on `data/examples/01.txt`
+/// and `part_two` on `data/examples/01-2.txt`, as tests named `test_part_one` and
+/// `test_part_two_2`. Suffixes go up to `-9`.
+#[macro_export]
+macro_rules! example_tests {
+    ($(($part:tt, $suffix:tt, $expected:expr)),+ $(,)?) => {
+        $( $crate::example_tests!(@suffix $part, $suffix, $expected); )+
+    };
+
+    (@suffix $part:tt, "", $expected:expr) => { $crate::example_tests!(@part $part, "", $expected, test_part_one, test_part_two); };
+    (@suffix $part:tt, "-2", $expected:expr) => { $crate::example_tests!(@part $part, "-2", $expected, test_part_one_2, test_part_two_2); };
+    (@suffix $part:tt, "-3", $expected:expr) => { $crate::example_tests!(@part $part, "-3", $expected, test_part_one_3, test_part_two_3); };
+    (@suffix $part:tt, "-4", $expected:expr) => { $crate::example_tests!(@part $part, "-4", $expected, test_part_one_4, test_part_two_4); };
+    (@suffix $part:tt, "-5", $expected:expr) => { $crate::example_tests!(@part $part, "-5", $expected, test_part_one_5, test_part_two_5); };
+    (@suffix $part:tt, "-6", $expected:expr) => { $crate::example_tests!(@part $part, "-6", $expected, test_part_one_6, test_part_two_6); };
+    (@suffix $part:tt, "-7", $expected:expr) => { $crate::example_tests!(@part $part, "-7", $expected, test_part_one_7, test_part_two_7); };
+    (@suffix $part:tt, "-8", $expected:expr) => { $crate::example_tests!(@part $part, "-8", $expected, test_part_one_8, test_part_two_8); };
+    (@suffix $part:tt, "-9", $expected:expr) => { $crate::example_tests!(@part $part, "-9", $expected, test_part_one_9, test_part_two_9); };
+
+    (@part 1, $suffix:tt, $expected:expr, $one:ident, $two:ident) => {
+        #[test]
+        fn $one() {
+            $crate::template::check_example(DAY, 1, $suffix, part_one, $expected);
+        }
+    };
+    (@part 2, $suffix:tt, $expected:expr, $one:ident, $two:ident) => {
+        #[test]
+        fn $two() {
+            $crate::template::check_example(DAY, 2, $suffix, part_two, $expected);
+        }
+    };
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
@@ -39,19 +104,72 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Alternatively, a part can list several implementations as named variants, e.g.
+/// `solution!(14, part_two = [part_two, part_two_naive])`. The first variant is the one that
+/// is solved and timed, `cargo compare` runs all of them and checks that their answers agree.
+///
+/// Any of these can end with `visualize = visualize`, naming a function that takes the input
+/// and the part and returns frames for `cargo viz` to show. See [`crate::visualize`].
+/// After that can come `render = render`, a function that takes the input and returns the
+/// images that `cargo solve NN --render FILE` saves. See [`crate::image`].
+/// Last can come `generate = generate`, a function that takes a size and a [`crate::random::Rng`]
+/// and returns a random input, which `--size N` solves instead of the real one.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, visualize = $viz:path)? $(, render = $render:path)? $(, generate = $gen:path)?) => {
+        $crate::solution!(@impl $day, [$($viz)?] [$($render)?] [$($gen)?] [1, part_one] [2, part_two]);
+    };
+    ($day:expr, 1 $(, visualize = $viz:path)? $(, render = $render:path)? $(, generate = $gen:path)?) => {
+        $crate::solution!(@impl $day, [$($viz)?] [$($render)?] [$($gen)?] [1, part_one]);
+    };
+    ($day:expr, 2 $(, visualize = $viz:path)? $(, render = $render:path)? $(, generate = $gen:path)?) => {
+        $crate::solution!(@impl $day, [$($viz)?] [$($render)?] [$($gen)?] [2, part_two]);
+    };
+    ($day:expr, part_one = [$($one:path),+ $(,)?], part_two = [$($two:path),+ $(,)?] $(, visualize = $viz:path)? $(, render = $render:path)? $(, generate = $gen:path)? $(,)?) => {
+        $crate::solution!(@impl $day, [$($viz)?] [$($render)?] [$($gen)?] [1, $($one),+] [2, $($two),+]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, part_one = [$($one:path),+ $(,)?] $(, visualize = $viz:path)? $(, render = $render:path)? $(, generate = $gen:path)? $(,)?) => {
+        $crate::solution!(@impl $day, [$($viz)?] [$($render)?] [$($gen)?] [1, $($one),+] [2, part_two]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr, part_two = [$($two:path),+ $(,)?] $(, visualize = $viz:path)? $(, render = $render:path)? $(, generate = $gen:path)? $(,)?) => {
+        $crate::solution!(@impl $day, [$($viz)?] [$($render)?] [$($gen)?] [1, part_one] [2, $($two),+]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@visualize $args:ident, $input:ident, []) => {
+        if $args.viz.is_some() {
+            eprintln!("Day {DAY} has no visualisation.");
+            std::process::exit(1);
+        }
+    };
+    (@visualize $args:ident, $input:ident, [$viz:path]) => {
+        if let Some(part) = $args.viz {
+            $crate::template::runner::visualize_part($viz(&$input, part), &$args);
+            return;
+        }
+    };
+
+    (@render $args:ident, $input:ident, []) => {
+        if $args.render.is_some() {
+            eprintln!("Day {DAY} has nothing to render.");
+            std::process::exit(1);
+        }
+    };
+    (@render $args:ident, $input:ident, [$render:path]) => {
+        if let Some(path) = &$args.render {
+            $crate::template::runner::render_images($render(&$input), path);
+        }
+    };
+
+    (@generate $args:ident, $size:ident, []) => {{
+        eprintln!("Day {DAY} has no input generator.");
+        std::process::exit(1)
+    }};
+    (@generate $args:ident, $size:ident, [$gen:path]) => {
+        $gen($size, &mut $crate::random::Rng::new($args.seed))
+    };
+
+    (@impl $day:expr, [$($viz:path)?] [$($render:path)?] [$($gen:path)?] $( [$part:expr, $func:path $(, $variant:path)*] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -61,8 +179,42 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let args = RunnerArgs::from_env();
+            let input = if let Some(size) = args.size {
+                $crate::solution!(@generate args, size, [$($gen)?])
+            } else if args.example {
+                $crate::template::read_file("examples", DAY)
+            } else {
+                $crate::template::read_file("inputs", DAY)
+            };
+
+            if args.print_input {
+                print!("{input}");
+                return;
+            }
+
+            $crate::solution!(@visualize args, input, [$($viz)?]);
+            $crate::solution!(@render args, input, [$($render)?]);
+
+            if args.compare {
+                let mut agree = true;
+                $(
+                    agree &= compare_part(
+                        &[
+                            (stringify!($func), &$func as &dyn Fn(&str) -> Option<_>),
+                            $( (stringify!($variant), &$variant as &dyn Fn(&str) -> Option<_>), )*
+                        ],
+                        &input,
+                        $part,
+                    );
+                )*
+                if !agree {
+                    std::process::exit(1);
+                }
+                return;
+            }
+
+            $( run_part($func, &input, DAY, $part, &args); )*
         }
     };
 }
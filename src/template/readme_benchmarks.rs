@@ -1,9 +1,9 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fmt::Display, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::run_multi::get_path_for_module;
+use crate::template::timings::{Memory, Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -13,6 +13,15 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -24,11 +33,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -51,26 +55,58 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b < 1024 => format!("{b}B"),
+        b if b < 1024 * 1024 => format!("{:.1}KiB", b as f64 / 1024.0),
+        b => format!("{:.1}MiB", b as f64 / (1024.0 * 1024.0)),
+    }
+}
+
+fn format_memory(memory: Option<Memory>) -> String {
+    match memory {
+        Some(m) => format!(
+            "`{}` peak, `{}` in {} allocs",
+            format_bytes(m.peak_bytes),
+            format_bytes(m.total_bytes),
+            m.allocations
+        ),
+        None => "-".into(),
+    }
+}
+
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
+    let has_memory = timings.has_memory();
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_memory {
+        lines.push("| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |".into());
+        lines.push("| :---: | :---: | :---:  | :---: | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let path = get_path_for_module(timing.day);
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        );
+        if has_memory {
+            line.push_str(&format!(
+                " {} | {} |",
+                format_memory(timing.memory_1),
+                format_memory(timing.memory_2)
+            ));
+        }
+        lines.push(line);
     }
 
     lines.push(String::new());
@@ -99,7 +135,10 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::timings::{Memory, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -109,18 +148,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    memory_1: None,
+                    memory_2: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    memory_1: None,
+                    memory_2: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    memory_1: None,
+                    memory_2: None,
                 },
             ],
         }
@@ -168,9 +213,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/days/day01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/days/day02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/days/day04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -179,4 +224,26 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].memory_1 = Some(Memory {
+            peak_bytes: 2048,
+            total_bytes: 3 * 1024 * 1024,
+            allocations: 12,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert_eq!(
+            s.contains("| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |"),
+            true
+        );
+        assert_eq!(s.contains("| [Day 1](./src/days/day01.rs) | `10ms` | `20ms` | `2.0KiB` peak, `3.0MiB` in 12 allocs | - |"), true);
+        assert_eq!(
+            s.contains("| [Day 2](./src/days/day02.rs) | `30ms` | `40ms` | - | - |"),
+            true
+        );
+    }
 }
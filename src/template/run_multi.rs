@@ -1,14 +1,23 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, fmt::Display, io, process};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{runner::RunnerArgs, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    answers::{AnswerRecord, Answers},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Runs the solutions for `days_to_run` with `args`, returning the answers they printed and,
+/// if `args.time` is set, their benchmark timings.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    args: &RunnerArgs,
+) -> (Answers, Option<Timings>) {
+    let is_timed = args.time;
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut answers: Vec<AnswerRecord> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
@@ -24,34 +33,96 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output = child_commands::run_solution(day, args, is_release, false)
+                .unwrap_or_else(|e| fail(day, &e));
 
             if output.is_empty() {
                 println!("Not solved.");
             } else {
+                answers.push(child_commands::parse_answers(&output, day));
                 let val = child_commands::parse_exec_time(&output, day);
                 timings.push(val);
             }
         });
 
+    let answers = Answers { data: answers };
+
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+        (answers, Some(timings))
     } else {
-        None
+        (answers, None)
     }
 }
 
+/// Measures the heap usage of the solutions for `days_to_run` with DHAT and records it in `timings`.
+/// Any input settings in `args` carry over.
+pub fn run_multi_memory(days_to_run: &HashSet<Day>, args: &RunnerArgs, timings: &mut Timings) {
+    let args = RunnerArgs {
+        memory: true,
+        time: false,
+        ..args.clone()
+    };
+
+    all_days()
+        .filter(|day| days_to_run.contains(day))
+        .for_each(|day| {
+            println!();
+            println!("{ANSI_BOLD}Day {day} (memory){ANSI_RESET}");
+            println!("---------------");
+
+            let output = child_commands::run_solution(day, &args, false, true)
+                .unwrap_or_else(|e| fail(day, &e));
+
+            if output.is_empty() {
+                println!("Not solved.");
+                return;
+            }
+
+            let index = timings
+                .data
+                .iter()
+                .position(|t| t.day == day)
+                .unwrap_or_else(|| {
+                    timings.data.push(Timing {
+                        day,
+                        part_1: None,
+                        part_2: None,
+                        total_nanos: 0_f64,
+                        memory_1: None,
+                        memory_2: None,
+                    });
+                    timings.data.len() - 1
+                });
+
+            child_commands::parse_memory(&output, &mut timings.data[index]);
+        });
+}
+
+fn fail(day: Day, e: &Error) -> ! {
+    eprintln!("failed to run day {day}: {e}");
+    process::exit(1);
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output of the solution."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -63,11 +134,19 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+/// The library module with a day's solution, which its binary runs.
+#[must_use]
+pub fn get_path_for_module(day: Day) -> String {
+    format!("./src/days/day{day}.rs")
+}
+
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        runner::RunnerArgs, timings::Memory, Answer, Day, ANSI_BOLD, ANSI_RESET,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -75,25 +154,39 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day, optionally in the DHAT profile.
+    pub fn run_solution(
+        day: Day,
+        runner_args: &RunnerArgs,
+        is_release: bool,
+        with_dhat: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
+        let mut args = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
+
+        if with_dhat {
+            args.extend([
+                "--profile".into(),
+                "dhat".into(),
+                "--features".into(),
+                "dhat-heap".into(),
+            ]);
+        } else if is_release {
+            args.push("--release".into());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        // mirror flags like `--time` to child invocations.
+        args.push("--".into());
+        args.extend(runner_args.to_args());
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
@@ -133,6 +226,8 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            memory_1: None,
+            memory_2: None,
         };
 
         output
@@ -163,6 +258,61 @@ pub mod child_commands {
         timings
     }
 
+    pub fn parse_answers(output: &[String], day: Day) -> super::AnswerRecord {
+        let mut answers = super::AnswerRecord {
+            day,
+            part_1: None,
+            part_2: None,
+        };
+
+        for line in output {
+            // intermediate results are overwritten with a carriage return, keep the last one.
+            let line = line.rsplit('\r').next().unwrap_or(line);
+
+            let Some((part, answer)) = parse_answer(line) else {
+                continue;
+            };
+
+            if part == "Part 1" {
+                answers.part_1 = Some(Answer::from(answer));
+            } else if part == "Part 2" {
+                answers.part_2 = Some(Answer::from(answer));
+            }
+        }
+
+        answers
+    }
+
+    pub fn parse_memory(output: &[String], timing: &mut super::Timing) {
+        for line in output {
+            let Some((part, memory)) = line.split_once(": ").and_then(|(part, rest)| {
+                let mut numbers = rest
+                    .split_ascii_whitespace()
+                    .filter_map(|word| word.parse::<u64>().ok());
+                let memory = Memory {
+                    peak_bytes: numbers.next()?,
+                    total_bytes: numbers.next()?,
+                    allocations: numbers.next()?,
+                };
+                rest.starts_with("peak ").then_some((part, memory))
+            }) else {
+                continue;
+            };
+
+            if part == "Part 1" {
+                timing.memory_1 = Some(memory);
+            } else if part == "Part 2" {
+                timing.memory_2 = Some(memory);
+            }
+        }
+    }
+
+    fn parse_answer(line: &str) -> Option<(&str, &str)> {
+        let (part, rest) = line.split_once(": ")?;
+        let answer = rest.strip_prefix(ANSI_BOLD)?.split(ANSI_RESET).next()?;
+        Some((part, answer))
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -173,7 +323,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -204,7 +354,8 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time, parse_memory};
+        use crate::template::{timings::Memory, Answer};
 
         use crate::day;
 
@@ -252,5 +403,40 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(
+                &[
+                    "Part 1: \x1b[1m142\x1b[0m > \x1b[3mbenching\x1b[0m\rPart 1: \x1b[1m142\x1b[0m (74.13ns @ 100000 samples)".into(),
+                    "Part 2: ✖             ".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), Answer::Integer(142));
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_memory() {
+            let mut timing = parse_exec_time(&[], day!(1));
+            parse_memory(
+                &[
+                    "Part 1: peak 2048 bytes, total 4096 bytes in 3 allocations".into(),
+                    "Part 2: measuring memory requires the `dhat-heap` feature.".into(),
+                ],
+                &mut timing,
+            );
+            assert_eq!(
+                timing.memory_1,
+                Some(Memory {
+                    peak_bytes: 2048,
+                    total_bytes: 4096,
+                    allocations: 3,
+                })
+            );
+            assert_eq!(timing.memory_2, None);
+        }
     }
 }
@@ -1,33 +1,331 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::RefCell;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
+use std::path::{Path, PathBuf};
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, process};
+#[cfg(feature = "cpu-profile")]
+use std::{fs, io};
 
+use clap::Parser;
+
+use crate::image::{self, Image};
+use crate::template::solve_times::{self, SolveTimes};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Answer, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::visualize::{Playback, Visualize};
+
+/// Flags that the `solve`, `all` and `time` commands pass on to a solution binary.
+#[derive(Parser, Debug, Default, Clone)]
+#[command(about = "Run a solution against its real input")]
+pub struct RunnerArgs {
+    /// Benchmark each part instead of running it once.
+    #[arg(long)]
+    pub time: bool,
+    /// Submit the answer for the given part via aoc-cli.
+    #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub submit: Option<u8>,
+    /// Measure the heap usage of each part instead of running it. Requires the `dhat-heap` feature.
+    #[arg(long, conflicts_with_all = ["time", "submit"])]
+    pub memory: bool,
+    /// Profile the given part with a sampling CPU profiler instead of running it. Requires the `cpu-profile` feature.
+    #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2), conflicts_with_all = ["time", "submit", "memory"])]
+    pub profile: Option<u8>,
+    /// Run every variant of each part, check that their answers agree and compare their timings.
+    #[arg(long, conflicts_with_all = ["time", "submit", "memory", "profile"])]
+    pub compare: bool,
+    /// How long to run the profiled part for, in seconds.
+    #[arg(long, default_value_t = 10, requires = "profile")]
+    pub seconds: u64,
+    /// Show the given part as an animation in the terminal instead of running it.
+    #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2), conflicts_with_all = ["time", "submit", "memory", "profile", "compare"])]
+    pub viz: Option<u8>,
+    /// Use the example instead of the real input.
+    #[arg(long, requires = "viz")]
+    pub example: bool,
+    /// Use a random input of this size instead of the real one, from the day's `generate` function.
+    #[arg(long, value_name = "N", conflicts_with_all = ["example", "submit"])]
+    pub size: Option<usize>,
+    /// The seed for the random input.
+    #[arg(long, default_value_t = 0, requires = "size")]
+    pub seed: u64,
+    /// Print the input instead of solving it.
+    #[arg(long, conflicts_with_all = ["time", "submit", "memory", "profile", "compare", "viz", "render"])]
+    pub print_input: bool,
+    /// How many frames to show each second.
+    #[arg(long, default_value_t = 10.0, requires = "viz")]
+    pub fps: f64,
+    /// Wait for Enter before showing each frame.
+    #[arg(long, requires = "viz")]
+    pub step: bool,
+    /// Leave out the colours.
+    #[arg(long, requires = "viz")]
+    pub plain: bool,
+    /// Write the frames to a text file instead of showing them.
+    #[arg(long, value_name = "FILE", requires = "viz")]
+    pub output: Option<PathBuf>,
+    /// Save a picture of the puzzle to this file before solving it, as a `.png`, `.ppm` or animated `.gif`.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["viz", "time", "memory", "profile", "compare"])]
+    pub render: Option<PathBuf>,
+}
+
+impl RunnerArgs {
+    /// Parses the flags from the arguments the solution binary was called with.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::parse()
+    }
+
+    /// Converts the flags back to the command-line arguments of a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if self.time {
+            args.push("--time".into());
+        }
+        if let Some(part) = self.submit {
+            args.push("--submit".into());
+            args.push(part.to_string());
+        }
+        if self.memory {
+            args.push("--memory".into());
+        }
+        if self.compare {
+            args.push("--compare".into());
+        }
+        if let Some(part) = self.profile {
+            args.push("--profile".into());
+            args.push(part.to_string());
+            args.push("--seconds".into());
+            args.push(self.seconds.to_string());
+        }
+        if let Some(part) = self.viz {
+            args.push("--viz".into());
+            args.push(part.to_string());
+            args.push("--fps".into());
+            args.push(self.fps.to_string());
+            if self.example {
+                args.push("--example".into());
+            }
+            if self.step {
+                args.push("--step".into());
+            }
+            if self.plain {
+                args.push("--plain".into());
+            }
+            if let Some(output) = &self.output {
+                args.push("--output".into());
+                args.push(output.display().to_string());
+            }
+        }
+        if let Some(render) = &self.render {
+            args.push("--render".into());
+            args.push(render.display().to_string());
+        }
+        if let Some(size) = self.size {
+            args.push("--size".into());
+            args.push(size.to_string());
+            args.push("--seed".into());
+            args.push(self.seed.to_string());
+        }
+        if self.print_input {
+            args.push("--print-input".into());
+        }
+        args
+    }
+
+    /// The settings for showing frames with `--viz`.
+    #[must_use]
+    pub fn playback(&self) -> Playback {
+        Playback {
+            fps: self.fps,
+            step: self.step,
+            plain: self.plain,
+            output: self.output.clone(),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Lets a solution part explain why it has no answer. Parts return an `Option`, so call
+/// `.reported()?` on a `Result`, and the runner prints the error next to the ✖. A
+/// visualisation or render that can't parse its input reports the error and returns nothing.
+pub trait Reported<T> {
+    fn reported(self) -> Option<T>;
+}
+
+impl<T, E: Display> Reported<T> for Result<T, E> {
+    fn reported(self) -> Option<T> {
+        self.map_err(|e| LAST_ERROR.set(Some(e.to_string()))).ok()
+    }
+}
+
+/// The error reported by the last part that failed, if it hasn't been shown yet.
+#[must_use]
+pub fn take_error() -> Option<String> {
+    LAST_ERROR.take()
+}
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: Into<Answer>>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    args: &RunnerArgs,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    if args.memory {
+        print_memory(func, input, &part_str);
+        return;
+    }
+
+    if let Some(profiled) = args.profile {
+        if profiled == part {
+            print_profile(func, input, day, part, Duration::from_secs(args.seconds));
+        }
+        return;
+    }
+
+    let (result, duration, samples) = run_timed(
+        |input| func(input).map(Into::into),
+        input,
+        args.time,
+        |result| print_result(result, &part_str, ""),
+    );
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        if args.submit == Some(part) {
+            match submit_result(&result, day, part) {
+                Ok(output) if aoc_cli::is_correct(&output) => record_solve(day, part),
+                Ok(_) => {}
+                Err(e) => eprintln!("failed to call aoc-cli: {e}"),
+            }
+        }
+    }
+}
+
+/// Exits with the error a visualisation or render reported, if there was one.
+fn exit_on_error() {
+    if let Some(error) = take_error() {
+        eprintln!("could not parse the input: {error}");
+        process::exit(1);
+    }
+}
+
+/// Show the frames a day's visualisation produced for a part, as set up by `args`.
+pub fn visualize_part<V: Visualize>(frames: impl IntoIterator<Item = V>, args: &RunnerArgs) {
+    exit_on_error();
+    let playback = args.playback();
+    match playback.play(frames) {
+        Ok(count) => {
+            if let Some(output) = &playback.output {
+                println!(
+                    "Wrote {count} frames to {ANSI_BOLD}{}{ANSI_RESET}",
+                    output.display()
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("could not show frames: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// How long each frame of a rendered GIF is shown for.
+const RENDER_DELAY: Duration = Duration::from_millis(100);
+
+/// Saves the images from a day's `render` function for `--render`.
+pub fn render_images(images: impl IntoIterator<Item = Image>, path: &Path) {
+    exit_on_error();
+    let images: Vec<Image> = images.into_iter().collect();
+    match image::save(&images, path, RENDER_DELAY) {
+        Ok(()) => println!(
+            "Rendered {} to {ANSI_BOLD}{}{ANSI_RESET}",
+            if images.len() == 1 {
+                "1 image".to_string()
+            } else {
+                format!("{} frames", images.len())
+            },
+            path.display()
+        ),
+        Err(e) => {
+            eprintln!("could not render {}: {e}", path.display());
+            process::exit(1);
+        }
     }
 }
 
+/// A named implementation of a solution part, as registered with the `solution!` macro.
+pub type Variant<'a, T> = (&'a str, &'a dyn Fn(&str) -> Option<T>);
+
+/// Benchmark every variant of a solution part on the same input and print a table with their
+/// answers and timings. Returns whether all variants agree with the first one.
+pub fn compare_part<T: Into<Answer>>(variants: &[Variant<T>], input: &str, part: u8) -> bool {
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+    let name_width = variants
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let mut runs = vec![];
+
+    for (name, func) in variants {
+        print!("{name:<name_width$}");
+        let (result, duration, samples) =
+            run_timed(|input| func(input).map(Into::into), input, true, |_| {});
+        print!("\r");
+        let _ = stdout().flush();
+        runs.push((name, result, duration, samples));
+    }
+
+    let answers: Vec<String> = runs
+        .iter()
+        .map(|(_, result, _, _)| match result {
+            Some(result) if result.to_string().contains('\n') => "▼".to_string(),
+            Some(result) => result.to_string(),
+            None => "✖".to_string(),
+        })
+        .collect();
+    let answer_width = answers.iter().map(|a| a.chars().count()).max().unwrap_or(0);
+    let fastest = runs.iter().map(|run| run.2).min().unwrap_or_default();
+    let agree = runs.iter().all(|run| run.1 == runs[0].1);
+
+    for ((name, result, duration, samples), answer) in runs.iter().zip(&answers) {
+        let mark = if *result == runs[0].1 { " " } else { "✖" };
+        #[allow(clippy::cast_precision_loss)]
+        let relative = duration.as_nanos() as f64 / cmp::max(fastest.as_nanos(), 1) as f64;
+
+        println!(
+            "{name:<name_width$}  {mark} {ANSI_BOLD}{answer:<answer_width$}{ANSI_RESET}  {relative:>6.2}x{ANSI_ITALIC}{}{ANSI_RESET}",
+            format_duration(duration, *samples)
+        );
+    }
+
+    if !agree {
+        eprintln!("Part {part}: variants disagree with {}.", runs[0].0);
+    }
+
+    agree
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
@@ -43,7 +341,7 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if is_timed {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
@@ -52,19 +350,115 @@ fn run_timed<I: Clone, T>(
     (result, run.0, run.1)
 }
 
+/// Run a solution part once under a DHAT heap profiler in testing mode, which reports
+/// the heap usage without writing a `dhat-heap.json` file.
+#[cfg(feature = "dhat-heap")]
+fn print_memory<I, T>(func: impl Fn(I) -> T, input: I, part: &str) {
+    let stats = {
+        let _profiler = dhat::Profiler::builder().testing().build();
+        black_box(func(black_box(input)));
+        dhat::HeapStats::get()
+    };
+
+    println!(
+        "{part}: peak {} bytes, total {} bytes in {} allocations",
+        stats.max_bytes, stats.total_bytes, stats.total_blocks
+    );
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn print_memory<I, T>(_func: impl Fn(I) -> T, _input: I, part: &str) {
+    eprintln!("{part}: measuring memory requires the `dhat-heap` feature.");
+}
+
+/// Run a solution part in a loop for `budget` under a sampling profiler, which interrupts the
+/// process with `SIGPROF` via `setitimer` and records a stack trace on every tick.
+/// Folded stacks and an SVG flamegraph are written to `data/profiles/`.
+#[cfg(feature = "cpu-profile")]
+fn print_profile<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    part: u8,
+    budget: Duration,
+) {
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(PROFILE_FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .expect("could not start profiler");
+
+    let timer = Instant::now();
+    let mut runs = 0;
+    while runs == 0 || timer.elapsed() < budget {
+        black_box(func(black_box(input.clone())));
+        runs += 1;
+    }
+    let elapsed = timer.elapsed();
+
+    let report = guard.report().build().expect("could not build profile");
+    drop(guard);
+
+    let path = format!("data/profiles/{day}-part{part}");
+    let written = fs::create_dir_all("data/profiles")
+        .and_then(|()| fs::write(format!("{path}.folded"), folded_stacks(&report).join("\n")))
+        .and_then(|()| fs::File::create(format!("{path}.svg")))
+        .and_then(|file| report.flamegraph(file).map_err(io::Error::other));
+
+    if let Err(e) = written {
+        eprintln!("could not write profile to {path}: {e}");
+        process::exit(1);
+    }
+
+    println!(
+        "Part {part}: {runs} runs in {elapsed:.1?}, wrote {ANSI_BOLD}{path}.svg{ANSI_RESET} and {path}.folded"
+    );
+}
+
+#[cfg(not(feature = "cpu-profile"))]
+fn print_profile<I, T>(_func: impl Fn(I) -> T, _input: I, _day: Day, part: u8, _budget: Duration) {
+    eprintln!("Part {part}: profiling requires the `cpu-profile` feature.");
+}
+
+#[cfg(feature = "cpu-profile")]
+const PROFILE_FREQUENCY: i32 = 997;
+
+/// Formats the samples of a profile as folded stacks, one line per unique stack with
+/// its frames separated by `;` from the thread down to the leaf, followed by the sample count.
+#[cfg(feature = "cpu-profile")]
+fn folded_stacks(report: &pprof::Report) -> Vec<String> {
+    let mut lines: Vec<String> = report
+        .data
+        .iter()
+        .map(|(frames, count)| {
+            let stack = frames
+                .frames
+                .iter()
+                .rev()
+                .flat_map(|frame| frame.iter().rev())
+                .map(ToString::to_string);
+
+            let stack = std::iter::once(frames.thread_name_or_id())
+                .chain(stack)
+                .collect::<Vec<_>>()
+                .join(";");
+
+            format!("{stack} {count}")
+        })
+        .collect();
+
+    lines.sort_unstable();
+    lines
+}
+
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -99,7 +493,7 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -128,47 +522,39 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 print!("{part}: ✖");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                match take_error() {
+                    Some(error) => println!("{part}: ✖ {error}"),
+                    None => println!("{part}: ✖             "),
+                }
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
-
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
-
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
-
-    if part_submit != part {
-        return None;
-    }
-
+/// Try to submit one part of the solution if aoc-cli is installed.
+fn submit_result(result: &Answer, day: Day, part: u8) -> Result<Output, aoc_cli::AocCommandError> {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    aoc_cli::submit(day, part, &result.to_string())
+}
+
+/// Saves when a part was answered correctly, and shows how long it took if the puzzle was
+/// opened with `cargo today --race`.
+fn record_solve(day: Day, part: u8) {
+    let mut times = SolveTimes::read_from_file();
+    times.solve(day, part, solve_times::now());
+    if let Err(e) = times.store_file() {
+        eprintln!("failed to store solve times: {e}");
+        return;
+    }
+    if let Some(elapsed) = times.get(day).and_then(|time| time.duration(part)) {
+        println!(
+            "Solved part {part} in {ANSI_BOLD}{}{ANSI_RESET}",
+            solve_times::format_elapsed(elapsed)
+        );
+    }
 }
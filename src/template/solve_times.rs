@@ -0,0 +1,315 @@
+/// Personal solve times: when a puzzle was opened with `cargo today --race` and when each part
+/// was answered correctly with `--submit`.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static SOLVE_TIMES_FILE_PATH: &str = "./data/solve_times.json";
+
+/// The current time, in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+/// Formats a duration as hours, minutes and seconds, like `1:02:03`.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Represents the solve times for a single day, as seconds since the Unix epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolveTime {
+    pub day: Day,
+    pub opened: Option<u64>,
+    pub part_1: Option<u64>,
+    pub part_2: Option<u64>,
+}
+
+impl SolveTime {
+    fn new(day: Day) -> Self {
+        SolveTime {
+            day,
+            opened: None,
+            part_1: None,
+            part_2: None,
+        }
+    }
+
+    /// When `part` was answered correctly.
+    pub fn part(&self, part: u8) -> Option<u64> {
+        if part == 1 {
+            self.part_1
+        } else {
+            self.part_2
+        }
+    }
+
+    /// How long after opening the puzzle `part` was answered.
+    pub fn duration(&self, part: u8) -> Option<Duration> {
+        let seconds = self.part(part)?.checked_sub(self.opened?)?;
+        Some(Duration::from_secs(seconds))
+    }
+
+    /// How long part two took after part one.
+    pub fn delta(&self) -> Option<Duration> {
+        let seconds = self.part_2?.checked_sub(self.part_1?)?;
+        Some(Duration::from_secs(seconds))
+    }
+}
+
+/// Represents the solve times for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct SolveTimes {
+    pub data: Vec<SolveTime>,
+}
+
+impl SolveTimes {
+    /// Dehydrate solve times to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(SOLVE_TIMES_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate solve times from a JSON file. If not present, returns no solve times.
+    pub fn read_from_file() -> Self {
+        let Ok(s) = fs::read_to_string(SOLVE_TIMES_FILE_PATH) else {
+            return SolveTimes::default();
+        };
+
+        match SolveTimes::try_from(s) {
+            Ok(times) => times,
+            Err(e) => {
+                eprintln!("{e}");
+                SolveTimes::default()
+            }
+        }
+    }
+
+    /// Returns the solve times stored for `day`, if any.
+    pub fn get(&self, day: Day) -> Option<&SolveTime> {
+        self.data.iter().find(|t| t.day == day)
+    }
+
+    /// The solve times for `day`, adding them in order if there are none yet. The file may
+    /// have been edited by hand, so it isn't assumed to be sorted.
+    fn entry(&mut self, day: Day) -> &mut SolveTime {
+        let index = match self.data.iter().position(|t| t.day == day) {
+            Some(index) => index,
+            None => {
+                let index = self.data.partition_point(|t| t.day < day);
+                self.data.insert(index, SolveTime::new(day));
+                index
+            }
+        };
+        &mut self.data[index]
+    }
+
+    /// Records when the puzzle for `day` was opened, unless it already was.
+    pub fn open(&mut self, day: Day, at: u64) {
+        self.entry(day).opened.get_or_insert(at);
+    }
+
+    /// Records when `part` of `day` was answered correctly, unless it already was.
+    pub fn solve(&mut self, day: Day, part: u8, at: u64) {
+        let time = self.entry(day);
+        let solved = if part == 1 {
+            &mut time.part_1
+        } else {
+            &mut time.part_2
+        };
+        solved.get_or_insert(at);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<SolveTimes> for JsonValue {
+    fn from(value: SolveTimes) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for SolveTimes {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(SolveTimes {
+            data: json_data
+                .iter()
+                .map(SolveTime::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&SolveTime> for JsonValue {
+    fn from(value: &SolveTime) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        #[allow(clippy::cast_precision_loss)]
+        for (key, time) in [
+            ("opened", value.opened),
+            ("part_1", value.part_1),
+            ("part_2", value.part_2),
+        ] {
+            map.insert(
+                key.into(),
+                time.map_or(JsonValue::Null, |t| JsonValue::Number(t as f64)),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for SolveTime {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected solve time to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected solve_time.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let time = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::Number(n)) if *n >= 0.0 => Ok(Some(*n as u64)),
+            Some(_) => Err(format!("Expected solve_time.{key} to be null or a number.")),
+        };
+
+        Ok(SolveTime {
+            day,
+            opened: time("opened")?,
+            part_1: time("part_1")?,
+            part_2: time("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    #[test]
+    fn records_first_times_only() {
+        let mut times = SolveTimes::default();
+        times.solve(day!(3), 1, 1_500);
+        times.open(day!(3), 1_000);
+        times.open(day!(1), 100);
+        times.open(day!(3), 2_000);
+        times.solve(day!(3), 2, 1_800);
+        times.solve(day!(3), 1, 1_900);
+
+        assert_eq!(times.data.len(), 2);
+        assert_eq!(times.data[0].day, day!(1));
+        let day_3 = times.get(day!(3)).unwrap();
+        assert_eq!(day_3.duration(1), Some(Duration::from_secs(500)));
+        assert_eq!(day_3.duration(2), Some(Duration::from_secs(800)));
+        assert_eq!(day_3.delta(), Some(Duration::from_secs(300)));
+        assert_eq!(times.get(day!(1)).unwrap().duration(1), None);
+    }
+
+    #[test]
+    fn finds_days_in_unsorted_file() {
+        let json = r#"{ "data": [
+            { "day": "09", "opened": 10 },
+            { "day": "02", "opened": 20 },
+            { "day": "05", "opened": 30 }
+        ] }"#;
+        let mut times = SolveTimes::try_from(json.to_string()).unwrap();
+        times.solve(day!(2), 1, 80);
+        times.solve(day!(9), 1, 40);
+
+        assert_eq!(times.data.len(), 3);
+        assert_eq!(
+            times.get(day!(2)).unwrap().duration(1),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            times.get(day!(9)).unwrap().duration(1),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn duration_needs_open_time() {
+        let mut times = SolveTimes::default();
+        times.solve(day!(5), 1, 100);
+        times.solve(day!(5), 2, 160);
+        let day_5 = times.get(day!(5)).unwrap();
+        assert_eq!(day_5.duration(1), None);
+        assert_eq!(day_5.delta(), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut times = SolveTimes::default();
+        times.open(day!(2), 1_701_493_200);
+        times.solve(day!(2), 1, 1_701_493_530);
+        times.solve(day!(4), 2, 1_701_666_000);
+
+        let json = JsonValue::from(times.clone()).stringify().unwrap();
+        let parsed = SolveTimes::try_from(json).unwrap();
+        assert_eq!(parsed.data, times.data);
+    }
+
+    #[test]
+    fn rejects_bad_times() {
+        let json = r#"{ "data": [{ "day": "01", "opened": "noon" }] }"#;
+        assert!(SolveTimes::try_from(json.to_string()).is_err());
+    }
+
+    #[test]
+    fn formats_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(59)), "0:00:59");
+        assert_eq!(format_elapsed(Duration::from_secs(3_723)), "1:02:03");
+        assert_eq!(format_elapsed(Duration::from_millis(61_900)), "0:01:01");
+    }
+}
@@ -0,0 +1,177 @@
+/// Collects a progress report for every day from the files in the repository.
+use std::{fs, path::Path};
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::get_path_for_module;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{all_days, Answer, Day};
+
+/// Represents the progress made on a single day.
+#[derive(Clone, Debug)]
+pub struct DayStats {
+    pub day: Day,
+    pub scaffolded: bool,
+    pub input: bool,
+    pub examples: usize,
+    pub part_1: Option<Answer>,
+    pub part_2: Option<Answer>,
+    pub tests: usize,
+    pub timing: Option<Timing>,
+}
+
+impl DayStats {
+    /// Describes everything that is still missing for this day.
+    pub fn missing(&self) -> Vec<&'static str> {
+        let mut missing = vec![];
+        if !self.scaffolded {
+            missing.push("solution");
+        }
+        if !self.input {
+            missing.push("input");
+        }
+        if self.examples == 0 {
+            missing.push("examples");
+        }
+        if self.part_1.is_none() {
+            missing.push("part 1");
+        }
+        if self.part_2.is_none() {
+            missing.push("part 2");
+        }
+        if self.tests == 0 {
+            missing.push("tests");
+        }
+        if self.timing.is_none() {
+            missing.push("timings");
+        }
+        missing
+    }
+}
+
+/// Gathers the progress for all days.
+pub fn collect() -> Vec<DayStats> {
+    let answers = Answers::read_from_file();
+    let timings = Timings::read_from_file();
+    let examples = list_dir("data/examples");
+
+    all_days()
+        .map(|day| {
+            let source = fs::read_to_string(get_path_for_module(day)).ok();
+            let answer = answers.get(day);
+
+            DayStats {
+                day,
+                scaffolded: source.is_some(),
+                input: is_non_empty(format!("data/inputs/{day}.txt")),
+                examples: count_examples(&examples, day),
+                part_1: answer.and_then(|a| a.part_1.clone()),
+                part_2: answer.and_then(|a| a.part_2.clone()),
+                tests: source.as_deref().map_or(0, count_tests),
+                timing: timings.data.iter().find(|t| t.day == day).cloned(),
+            }
+        })
+        .collect()
+}
+
+fn is_non_empty(path: impl AsRef<Path>) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+fn list_dir(path: &str) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(path) else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| is_non_empty(entry.path()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect()
+}
+
+/// Counts example files for `day`, both `01.txt` and variants like `01-2.txt`.
+fn count_examples(files: &[String], day: Day) -> usize {
+    let exact = format!("{day}.txt");
+    let variant = format!("{day}-");
+
+    files
+        .iter()
+        .filter(|f| **f == exact || (f.starts_with(&variant) && f.ends_with(".txt")))
+        .count()
+}
+
+/// Counts test functions and individual `test_case` attributes in a solution.
+fn count_tests(source: &str) -> usize {
+    let attributes = source
+        .lines()
+        .map(str::trim_start)
+        .filter(|l| l.starts_with("#[test]") || l.starts_with("#[test_case("))
+        .count();
+    attributes + count_example_rows(source)
+}
+
+/// Counts the `(part, suffix, expected)` rows of every `example_tests!`, which are a test each.
+fn count_example_rows(source: &str) -> usize {
+    let mut rows = 0;
+    for (start, call) in source.match_indices("example_tests!(") {
+        let line = source[..start].rsplit('\n').next().unwrap_or_default();
+        if line.trim_start().starts_with("//") {
+            continue;
+        }
+        let mut depth = 0;
+        for c in source[start + call.len()..].chars() {
+            match c {
+                '(' if depth == 0 => {
+                    rows += 1;
+                    depth += 1;
+                }
+                '(' => depth += 1,
+                ')' if depth == 0 => break,
+                ')' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+    rows
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{count_examples, count_tests};
+    use crate::day;
+
+    #[test]
+    fn counts_examples() {
+        let files = [
+            "01.txt".to_string(),
+            "01-2.txt".to_string(),
+            "10.txt".to_string(),
+            "11-2.txt".to_string(),
+            ".keep".to_string(),
+        ];
+        assert_eq!(count_examples(&files, day!(1)), 2);
+        assert_eq!(count_examples(&files, day!(10)), 1);
+        assert_eq!(count_examples(&files, day!(11)), 1);
+        assert_eq!(count_examples(&files, day!(2)), 0);
+    }
+
+    #[test]
+    fn counts_tests_and_cases() {
+        let source = r#"
+            #[test]
+            fn test_part_one() {}
+
+            #[test_case("a" => 1)]
+            #[test_case("b" => 2)]
+            fn test_cases(s: &str) -> usize {}
+
+            crate::example_tests!(
+                (1, "", 142),
+                (1, "-2", 6),
+                (2, "-2", 281)
+            );
+            // crate::example_tests!((2, "-3", 0));
+        "#;
+        assert_eq!(count_tests(source), 6);
+    }
+}
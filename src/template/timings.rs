@@ -12,6 +12,16 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub memory_1: Option<Memory>,
+    pub memory_2: Option<Memory>,
+}
+
+/// Represents the heap usage of a single part, as measured by DHAT.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Memory {
+    pub peak_bytes: u64,
+    pub total_bytes: u64,
+    pub allocations: u64,
 }
 
 /// Represents benchmark times for a set of days.
@@ -31,11 +41,11 @@ impl Timings {
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
+        let Ok(s) = fs::read_to_string(TIMINGS_FILE_PATH) else {
+            return Timings::default();
+        };
 
-        match s {
+        match Timings::try_from(s) {
             Ok(timings) => timings,
             Err(e) => {
                 eprintln!("{e}");
@@ -45,11 +55,17 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Memory measurements are kept from `self` if `other` has none for a part.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if let Some(old) = self.data.iter().find(|t| t.day == timing.day) {
+                timing.memory_1 = timing.memory_1.or(old.memory_1);
+                timing.memory_2 = timing.memory_2.or(old.memory_2);
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -58,7 +74,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -72,6 +88,13 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Whether any of the timings include memory measurements.
+    pub fn has_memory(&self) -> bool {
+        self.data
+            .iter()
+            .any(|t| t.memory_1.is_some() || t.memory_2.is_some())
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -140,6 +163,14 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(memory) = &value.memory_1 {
+            map.insert("memory_1".into(), JsonValue::from(memory));
+        }
+
+        if let Some(memory) = &value.memory_2 {
+            map.insert("memory_2".into(), JsonValue::from(memory));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -173,11 +204,60 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // memory is optional, timings stored without `--memory` don't have it.
+        let memory_1 = json.get("memory_1").map(Memory::try_from).transpose()?;
+        let memory_2 = json.get("memory_2").map(Memory::try_from).transpose()?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            memory_1,
+            memory_2,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Memory> for JsonValue {
+    fn from(value: &Memory) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        for (key, bytes) in [
+            ("peak_bytes", value.peak_bytes),
+            ("total_bytes", value.total_bytes),
+            ("allocations", value.allocations),
+        ] {
+            map.insert(key.into(), JsonValue::Number(bytes as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Memory {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(Memory {
+            peak_bytes: get("peak_bytes")?,
+            total_bytes: get("total_bytes")?,
+            allocations: get("allocations")?,
         })
     }
 }
@@ -198,25 +278,34 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    memory_1: None,
+                    memory_2: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    memory_1: None,
+                    memory_2: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    memory_1: None,
+                    memory_2: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{Memory, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -230,6 +319,22 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "memory_1": { "peak_bytes": 2048, "total_bytes": 4096, "allocations": 3 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.memory_1,
+                Some(Memory {
+                    peak_bytes: 2048,
+                    total_bytes: 4096,
+                    allocations: 3
+                })
+            );
+            assert_eq!(timing.memory_2, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -289,10 +394,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    memory_1: None,
+                    memory_2: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -303,10 +410,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    memory_1: None,
+                    memory_2: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -317,17 +426,19 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    memory_1: None,
+                    memory_2: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{Memory, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -341,6 +452,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    memory_1: None,
+                    memory_2: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +474,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    memory_1: None,
+                    memory_2: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -372,6 +487,21 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_stored_memory() {
+            let memory = Memory {
+                peak_bytes: 1,
+                total_bytes: 2,
+                allocations: 3,
+            };
+            let mut timings = get_mock_timings();
+            timings.data[0].memory_1 = Some(memory);
+
+            let merged = timings.merge(&get_mock_timings());
+            assert_eq!(merged.data[0].memory_1, Some(memory));
+            assert_eq!(merged.data[0].memory_2, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,352 @@
+/// Drawing the state of a puzzle in the terminal, one frame at a time, for `cargo viz`.
+use std::fmt::{self, Display, Write as _};
+use std::fs;
+use std::io::{self, stdin, stdout, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crate::grid::{Grid, Pos};
+use crate::template::ANSI_RESET;
+
+/// The terminal colours a cell can be drawn in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    fn ansi(self) -> &'static str {
+        match self {
+            Colour::Red => "\x1b[31m",
+            Colour::Green => "\x1b[32m",
+            Colour::Yellow => "\x1b[33m",
+            Colour::Blue => "\x1b[34m",
+            Colour::Magenta => "\x1b[35m",
+            Colour::Cyan => "\x1b[36m",
+            Colour::White => "\x1b[97m",
+            Colour::Grey => "\x1b[90m",
+        }
+    }
+}
+
+/// One character of a frame, in the terminal's default colour if it has none.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub colour: Option<Colour>,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            symbol: ' ',
+            colour: None,
+        }
+    }
+}
+
+/// A picture of the puzzle at one moment, as a grid of coloured characters with a caption.
+/// `Display` gives the plain text, [`Frame::to_ansi`] adds the colours.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    cells: Grid<Cell>,
+    caption: String,
+}
+
+/// Box-drawing characters, indexed by which of north, east, south and west (bits 0 to 3)
+/// a piece of path joins up with.
+const BOX_DRAWING: [char; 16] = [
+    '·', '│', '─', '└', '│', '│', '┌', '├', '─', '┘', '─', '┴', '┐', '┤', '┬', '┼',
+];
+
+impl Frame {
+    /// A blank frame.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            cells: Grid::filled(width, height, Cell::default()),
+            caption: String::new(),
+        }
+    }
+
+    /// A frame with a character for every cell of `grid`.
+    pub fn from_grid<T>(grid: &Grid<T>, mut draw: impl FnMut(&T) -> char) -> Self {
+        Self {
+            cells: grid.map(|value| Cell {
+                symbol: draw(value),
+                colour: None,
+            }),
+            caption: String::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    #[must_use]
+    pub fn caption(&self) -> &str {
+        &self.caption
+    }
+
+    #[must_use]
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// The cell at `pos`. Panics if it is outside the frame.
+    #[must_use]
+    pub fn cell(&self, pos: Pos) -> Cell {
+        self.cells[pos]
+    }
+
+    /// Draws `symbol` at `pos`, in `colour`. Anything outside the frame is left off.
+    pub fn draw(&mut self, pos: Pos, symbol: char, colour: Option<Colour>) {
+        if let Some(cell) = self.cells.get_mut(pos) {
+            *cell = Cell { symbol, colour };
+        }
+    }
+
+    /// Changes the colour of whatever is already drawn at `pos`.
+    pub fn paint(&mut self, pos: Pos, colour: Colour) {
+        if let Some(cell) = self.cells.get_mut(pos) {
+            cell.colour = Some(colour);
+        }
+    }
+
+    /// Draws a line through each position in turn with box-drawing characters. Steps
+    /// between positions that aren't next to each other are left as gaps, and to close
+    /// a loop, finish with the first position again.
+    pub fn draw_path(&mut self, path: &[Pos], colour: Colour) {
+        let mut joins: Grid<u8> = Grid::filled(self.width(), self.height(), 0);
+        for pair in path.windows(2) {
+            let [a, b] = [pair[0], pair[1]];
+            let way = match (b.0.wrapping_sub(a.0), b.1.wrapping_sub(a.1)) {
+                (usize::MAX, 0) => 0,
+                (0, 1) => 1,
+                (1, 0) => 2,
+                (0, usize::MAX) => 3,
+                _ => continue,
+            };
+            let back = (way + 2) % 4;
+            if let Some(joined) = joins.get_mut(a) {
+                *joined |= 1 << way;
+            }
+            if let Some(joined) = joins.get_mut(b) {
+                *joined |= 1 << back;
+            }
+        }
+        for &pos in path {
+            if let Some(&joined) = joins.get(pos) {
+                self.draw(pos, BOX_DRAWING[joined as usize], Some(colour));
+            }
+        }
+    }
+
+    /// The frame with colours, for showing in a terminal.
+    #[must_use]
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        if !self.caption.is_empty() {
+            writeln!(out, "{}", self.caption).unwrap();
+        }
+        for row in self.cells.rows() {
+            let mut current = None;
+            for cell in row {
+                if cell.colour != current {
+                    out.push_str(cell.colour.map_or(ANSI_RESET, Colour::ansi));
+                    current = cell.colour;
+                }
+                out.push(cell.symbol);
+            }
+            if current.is_some() {
+                out.push_str(ANSI_RESET);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.caption.is_empty() {
+            writeln!(f, "{}", self.caption)?;
+        }
+        for row in self.cells.rows() {
+            let line: String = row.iter().map(|cell| cell.symbol).collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Something that can be drawn as a [`Frame`], such as the state of a simulation.
+///
+/// A day opts in by passing a function to the `solution!` macro with
+/// `visualize = visualize`. It's given the input and the part, and returns the frames to
+/// show in order, as anything that iterates over values implementing this trait.
+pub trait Visualize {
+    fn frame(&self) -> Frame;
+}
+
+impl Visualize for Frame {
+    fn frame(&self) -> Frame {
+        self.clone()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// How to show a run of frames.
+#[derive(Clone, Debug)]
+pub struct Playback {
+    /// Frames shown each second.
+    pub fps: f64,
+    /// Wait for Enter before each frame, instead of going at `fps`.
+    pub step: bool,
+    /// Leave out the colours.
+    pub plain: bool,
+    /// Write the frames to this file as plain text, instead of showing them.
+    pub output: Option<PathBuf>,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            fps: 10.0,
+            step: false,
+            plain: false,
+            output: None,
+        }
+    }
+}
+
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[2J";
+
+impl Playback {
+    /// Shows each frame in turn, or writes them all to the output file. Returns how many
+    /// frames there were, stopping early if `q` is entered while stepping.
+    pub fn play<V: Visualize>(&self, frames: impl IntoIterator<Item = V>) -> io::Result<usize> {
+        match &self.output {
+            Some(path) => {
+                let mut text = String::new();
+                let mut count = 0;
+                for (i, frame) in frames.into_iter().enumerate() {
+                    writeln!(text, "--- frame {} ---\n{}", i + 1, frame.frame()).unwrap();
+                    count += 1;
+                }
+                fs::write(path, text)?;
+                Ok(count)
+            }
+            None => self.show(frames),
+        }
+    }
+
+    fn show<V: Visualize>(&self, frames: impl IntoIterator<Item = V>) -> io::Result<usize> {
+        let mut stdout = stdout().lock();
+        let delay = Duration::from_secs_f64(1.0 / self.fps.max(0.01));
+        let mut count = 0;
+        for frame in frames {
+            let frame = frame.frame();
+            let text = if self.plain {
+                frame.to_string()
+            } else {
+                frame.to_ansi()
+            };
+            count += 1;
+            write!(stdout, "{CLEAR_SCREEN}{text}frame {count}")?;
+            stdout.flush()?;
+
+            if self.step {
+                write!(stdout, " (enter for the next, q to quit) ")?;
+                stdout.flush()?;
+                let mut line = String::new();
+                stdin().read_line(&mut line)?;
+                if line.trim() == "q" {
+                    break;
+                }
+            } else {
+                thread::sleep(delay);
+            }
+        }
+        writeln!(stdout)?;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_path() {
+        let mut frame = Frame::new(3, 3).with_caption("loop");
+        let square = [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (1, 2),
+            (2, 2),
+            (2, 1),
+            (1, 1),
+            (1, 0),
+        ];
+        let mut path = square.to_vec();
+        path.push((0, 0));
+        frame.draw_path(&path, Colour::Green);
+        assert_eq!(frame.to_string(), "loop\n┌─┐\n└┐│\n └┘\n");
+
+        // A path crossing itself, and a gap where it jumps
+        let mut frame = Frame::new(3, 3);
+        frame.draw_path(
+            &[(1, 0), (1, 1), (1, 2), (2, 2), (0, 1), (1, 1), (2, 1)],
+            Colour::Red,
+        );
+        assert_eq!(frame.to_string(), " │ \n─┼┐\n ││\n");
+    }
+
+    #[test]
+    fn test_to_ansi() {
+        let grid = Grid::parse("#.\n.#", |b| b);
+        let mut frame = Frame::from_grid(&grid, |&b| b as char);
+        frame.paint((0, 0), Colour::Grey);
+        frame.draw((0, 1), 'O', Some(Colour::Grey));
+        frame.draw((5, 5), 'X', None);
+        assert_eq!(frame.cell((0, 1)).symbol, 'O');
+        assert_eq!(frame.to_string(), "#O\n.#\n");
+        assert_eq!(frame.to_ansi(), "\x1b[90m#O\x1b[0m\n.#\n");
+    }
+
+    #[test]
+    fn test_play_to_file() {
+        let path = std::env::temp_dir().join(format!("aoc-viz-{}.txt", std::process::id()));
+        let playback = Playback {
+            output: Some(path.clone()),
+            ..Default::default()
+        };
+        let frames = (1..=2).map(|i| Frame::new(i, 1).with_caption(format!("step {i}")));
+        assert_eq!(playback.play(frames).unwrap(), 2);
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(path).unwrap();
+        assert_eq!(
+            written,
+            "--- frame 1 ---\nstep 1\n \n\n--- frame 2 ---\nstep 2\n  \n\n"
+        );
+    }
+}